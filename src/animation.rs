@@ -0,0 +1,290 @@
+use std::collections::HashMap;
+
+use bevy::asset::LoadState;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity as BodyVelocity;
+
+use crate::{components::Health, movement::Velocity, GameplaySet};
+
+// Walk clips never play slower or faster than this multiple of their base rate.
+const MIN_WALK_RATE: f32 = 0.25;
+const MAX_WALK_RATE: f32 = 2.0;
+// Below this speed an entity counts as standing still.
+const IDLE_SPEED: f32 = 1.0;
+
+/// A run of frames in a sprite sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationClip {
+    pub first: usize,
+    pub last: usize,
+    pub fps: u32,
+}
+
+impl AnimationClip {
+    fn len(&self) -> usize {
+        self.last - self.first + 1
+    }
+}
+
+/// Texture atlas and clips for one archetype, referenced by asset path.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpriteSheet {
+    pub path: &'static str,
+    pub tile_size: UVec2,
+    pub columns: u32,
+    pub rows: u32,
+    pub walk: AnimationClip,
+    pub hit: AnimationClip,
+    pub death: AnimationClip,
+    /// Movement speed at which the walk clip plays at its base rate.
+    pub reference_speed: f32,
+}
+
+impl SpriteSheet {
+    /// The usual layout: square tiles, one row each for walk, hit and death,
+    /// with `frames` frames per row.
+    pub const fn standard(path: &'static str, tile: u32, frames: u32, reference_speed: f32) -> Self {
+        let row = frames as usize;
+        Self {
+            path,
+            tile_size: UVec2::new(tile, tile),
+            columns: frames,
+            rows: 3,
+            walk: AnimationClip { first: 0, last: row - 1, fps: 10 },
+            hit: AnimationClip { first: row, last: 2 * row - 1, fps: 15 },
+            death: AnimationClip { first: 2 * row, last: 3 * row - 1, fps: 12 },
+            reference_speed,
+        }
+    }
+
+    fn clip(&self, kind: ClipKind) -> AnimationClip {
+        match kind {
+            ClipKind::Walk => self.walk,
+            ClipKind::Hit => self.hit,
+            ClipKind::Death => self.death,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipKind {
+    Walk,
+    Hit,
+    Death,
+}
+
+/// Plays clips from a `SpriteSheet` on this entity's sprite.
+///
+/// Until the sheet's texture has loaded (or if it fails to load) the entity
+/// keeps its plain colored sprite, so missing art never hides gameplay.
+#[derive(Component)]
+pub struct SpriteAnimation {
+    pub sheet: SpriteSheet,
+    clip: ClipKind,
+    frame: usize,
+    elapsed: f32,
+    last_health: Option<f32>,
+}
+
+impl SpriteAnimation {
+    pub fn new(sheet: SpriteSheet) -> Self {
+        Self::playing(sheet, ClipKind::Walk)
+    }
+
+    pub fn playing(sheet: SpriteSheet, clip: ClipKind) -> Self {
+        Self {
+            sheet,
+            clip,
+            frame: 0,
+            elapsed: 0.0,
+            last_health: None,
+        }
+    }
+
+    fn play(&mut self, clip: ClipKind) {
+        self.clip = clip;
+        self.frame = 0;
+        self.elapsed = 0.0;
+    }
+
+    fn atlas_index(&self) -> usize {
+        self.sheet.clip(self.clip).first + self.frame
+    }
+}
+
+/// Despawn the entity once its current (non-looping) clip has played through.
+#[derive(Component)]
+pub struct DespawnOnAnimationEnd;
+
+/// Sheet texture that is still loading.
+#[derive(Component)]
+struct PendingSheet {
+    image: Handle<Image>,
+    layout: Handle<TextureAtlasLayout>,
+}
+
+/// Loaded handles per sheet path, shared by every entity using the sheet.
+#[derive(Resource, Default)]
+struct SheetHandles(HashMap<&'static str, (Handle<Image>, Handle<TextureAtlasLayout>)>);
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SheetHandles>()
+            .add_systems(Update, (request_sheets, apply_loaded_sheets).chain())
+            .add_systems(
+                Update,
+                (trigger_hit_clips, animate_sprites)
+                    .chain()
+                    .after(apply_loaded_sheets)
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+/// Spawn a short-lived entity playing the death clip where an animated entity died.
+pub fn spawn_death_animation(
+    commands: &mut Commands,
+    sheet: SpriteSheet,
+    sprite: Sprite,
+    transform: Transform,
+) {
+    commands.spawn((
+        SpriteBundle {
+            sprite,
+            transform,
+            ..default()
+        },
+        SpriteAnimation::playing(sheet, ClipKind::Death),
+        DespawnOnAnimationEnd,
+    ));
+}
+
+fn request_sheets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+    mut handles: ResMut<SheetHandles>,
+    query: Query<(Entity, &SpriteAnimation), Added<SpriteAnimation>>,
+) {
+    for (entity, animation) in query.iter() {
+        let sheet = animation.sheet;
+        let (image, layout) = handles
+            .0
+            .entry(sheet.path)
+            .or_insert_with(|| {
+                let layout = TextureAtlasLayout::from_grid(
+                    sheet.tile_size,
+                    sheet.columns,
+                    sheet.rows,
+                    None,
+                    None,
+                );
+                (asset_server.load(sheet.path), layouts.add(layout))
+            })
+            .clone();
+        commands.entity(entity).insert(PendingSheet { image, layout });
+    }
+}
+
+fn apply_loaded_sheets(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut query: Query<(Entity, &PendingSheet, &SpriteAnimation, &mut Sprite, &mut Handle<Image>)>,
+) {
+    for (entity, pending, animation, mut sprite, mut texture) in query.iter_mut() {
+        match asset_server.load_state(&pending.image) {
+            LoadState::Loaded => {
+                *texture = pending.image.clone();
+                sprite.color = Color::WHITE;
+                commands
+                    .entity(entity)
+                    .insert(TextureAtlas {
+                        layout: pending.layout.clone(),
+                        index: animation.atlas_index(),
+                    })
+                    .remove::<PendingSheet>();
+            }
+            LoadState::Failed(_) => {
+                // Keep the solid-color fallback.
+                commands.entity(entity).remove::<PendingSheet>();
+            }
+            _ => {}
+        }
+    }
+}
+
+fn trigger_hit_clips(mut query: Query<(&Health, &mut SpriteAnimation), Changed<Health>>) {
+    for (health, mut animation) in query.iter_mut() {
+        let took_damage = animation.last_health.is_some_and(|last| health.value < last);
+        animation.last_health = Some(health.value);
+        if took_damage && animation.clip == ClipKind::Walk {
+            animation.play(ClipKind::Hit);
+        }
+    }
+}
+
+fn animate_sprites(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(
+        Entity,
+        &mut SpriteAnimation,
+        &mut Sprite,
+        Option<&mut TextureAtlas>,
+        Option<&Velocity>,
+        Option<&BodyVelocity>,
+        Has<DespawnOnAnimationEnd>,
+    )>,
+) {
+    for (entity, mut animation, mut sprite, atlas, velocity, body_velocity, despawn_on_end) in
+        query.iter_mut()
+    {
+        let velocity = velocity
+            .map(|v| v.0)
+            .or(body_velocity.map(|v| v.linvel))
+            .unwrap_or(Vec2::ZERO);
+        let speed = velocity.length();
+
+        // Face the direction of travel; keep the last facing while moving vertically.
+        if velocity.x.abs() > IDLE_SPEED {
+            sprite.flip_x = velocity.x < 0.0;
+        }
+
+        let clip = animation.sheet.clip(animation.clip);
+        let rate = match animation.clip {
+            ClipKind::Walk if speed < IDLE_SPEED => 0.0,
+            ClipKind::Walk => (speed / animation.sheet.reference_speed).clamp(MIN_WALK_RATE, MAX_WALK_RATE),
+            ClipKind::Hit | ClipKind::Death => 1.0,
+        };
+
+        animation.elapsed += time.delta_seconds() * rate;
+        let frame_time = 1.0 / clip.fps as f32;
+        while animation.elapsed >= frame_time {
+            animation.elapsed -= frame_time;
+            animation.frame += 1;
+            if animation.frame < clip.len() {
+                continue;
+            }
+            match animation.clip {
+                ClipKind::Walk => animation.frame = 0,
+                ClipKind::Hit => {
+                    animation.play(ClipKind::Walk);
+                    break;
+                }
+                ClipKind::Death => {
+                    animation.frame = clip.len() - 1;
+                    if despawn_on_end {
+                        commands.entity(entity).despawn_recursive();
+                    }
+                    break;
+                }
+            }
+        }
+
+        if let Some(mut atlas) = atlas {
+            atlas.index = animation.atlas_index();
+        }
+    }
+}
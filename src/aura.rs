@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use bevy::sprite::{MaterialMesh2dBundle, Mesh2dHandle};
+
+use crate::{
+    behavior::Slowed,
+    combat::{DamageEvent, DamageKind},
+    spatial::{SpatialIndex, SpatialKind},
+    weapon::{
+        StatChange, WeaponAppExt, WeaponDefinition, WeaponId, WeaponInstance, WeaponPart,
+        WeaponStats,
+    },
+    GameplaySet,
+};
+
+const AURA_RADIUS: f32 = 80.0;
+
+/// Damage field centered on its owner. Hits every enemy inside `radius` once
+/// per tick.
+#[derive(Component)]
+pub struct Aura {
+    tick: Timer,
+    radius: f32,
+    damage: f32,
+    // Speed multiplier for enemies inside, if the aura slows.
+    slow: Option<f32>,
+}
+
+pub struct AuraPlugin;
+
+impl Plugin for AuraPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_weapon(
+            WeaponDefinition {
+                id: WeaponId::AURA,
+                name: "Aura",
+                amount_label: "aura",
+                base_stats: WeaponStats {
+                    damage: 4.0,
+                    // Time between damage ticks.
+                    cooldown: 0.5,
+                    ..default()
+                },
+                levels: &[
+                    &[StatChange::Area(0.2)],
+                    &[StatChange::Damage(2.0), StatChange::Slow(0.4)],
+                    &[StatChange::Area(0.2)],
+                    &[StatChange::Damage(2.0)],
+                    &[StatChange::Area(0.2)],
+                    &[StatChange::Cooldown(-0.2)],
+                    &[StatChange::Area(0.2)],
+                ],
+                spawn: spawn_aura,
+            },
+            damage_in_aura,
+        )
+        .add_systems(Update, attach_aura_visuals.in_set(GameplaySet));
+    }
+}
+
+fn spawn_aura(commands: &mut Commands, owner: Entity, instance: &WeaponInstance) {
+    let stats = instance.stats;
+    let aura = commands
+        .spawn(Aura {
+            tick: Timer::from_seconds(stats.cooldown, TimerMode::Repeating),
+            radius: AURA_RADIUS * stats.area,
+            damage: stats.damage,
+            slow: (stats.slow > 0.0).then_some(1.0 - stats.slow),
+        })
+        .insert(SpatialBundle::from_transform(Transform::from_xyz(0.0, 0.0, -1.0)))
+        .insert(WeaponPart {
+            weapon: WeaponId::AURA,
+            owner,
+        })
+        .id();
+    commands.entity(owner).add_child(aura);
+}
+
+/// Give new auras a translucent circle, since weapon spawn functions can't reach the asset stores.
+fn attach_aura_visuals(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    query: Query<(Entity, &Aura, &Transform), Added<Aura>>,
+) {
+    for (entity, aura, transform) in query.iter() {
+        commands.entity(entity).insert(MaterialMesh2dBundle {
+            mesh: Mesh2dHandle(meshes.add(Circle::new(aura.radius))),
+            material: materials.add(Color::srgba(0.9, 0.6, 1.0, 0.2)),
+            transform: *transform,
+            ..default()
+        });
+    }
+}
+
+fn damage_in_aura(
+    mut commands: Commands,
+    time: Res<Time>,
+    index: Res<SpatialIndex>,
+    mut damage_events: EventWriter<DamageEvent>,
+    mut aura_query: Query<(&GlobalTransform, &mut Aura)>,
+) {
+    for (transform, mut aura) in aura_query.iter_mut() {
+        if !aura.tick.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let center = transform.translation().truncate();
+        let tick_seconds = aura.tick.duration().as_secs_f32();
+        index.for_each_within(center, aura.radius, SpatialKind::Enemy, |entry| {
+            damage_events.send(DamageEvent {
+                target: entry.entity,
+                amount: aura.damage,
+                kind: DamageKind::Physical,
+            });
+            if let Some(factor) = aura.slow {
+                // Lasts a little past the next tick so enemies stay slowed while inside.
+                commands
+                    .entity(entry.entity)
+                    .try_insert(Slowed::new(factor, tick_seconds * 1.5));
+            }
+        });
+    }
+}
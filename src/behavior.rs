@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+
+use crate::GameplaySet;
+
+/// Id of the AI driving an enemy. Each archetype picks one in its `EnemyStats`.
+///
+/// Behaviors are plain systems registered with
+/// [`EnemyBehaviorAppExt::register_enemy_behavior`], so other plugins can add
+/// their own AI without touching `enemy.rs`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EnemyBehavior(pub &'static str);
+
+impl EnemyBehavior {
+    /// Walk towards the player, following the flow field around obstacles.
+    pub const CHASE: Self = Self("chase");
+    /// Run in a straight line across the screen (formation stampedes).
+    pub const STAMPEDE: Self = Self("stampede");
+    /// Hold position at range from the player and periodically summon minions.
+    pub const SUMMON: Self = Self("summon");
+}
+
+/// All behaviors that have a system driving them.
+#[derive(Resource, Default)]
+pub struct EnemyBehaviorRegistry {
+    behaviors: HashSet<EnemyBehavior>,
+}
+
+impl EnemyBehaviorRegistry {
+    pub fn contains(&self, behavior: EnemyBehavior) -> bool {
+        self.behaviors.contains(&behavior)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EnemyBehavior> {
+        self.behaviors.iter()
+    }
+}
+
+/// Multiplies an enemy's movement speed by `factor` until `timer` finishes.
+/// Applied after the behavior systems, so every behavior respects it.
+#[derive(Component, Debug, Clone)]
+pub struct Slowed {
+    pub factor: f32,
+    pub timer: Timer,
+}
+
+impl Slowed {
+    pub fn new(factor: f32, seconds: f32) -> Self {
+        Self {
+            factor,
+            timer: Timer::from_seconds(seconds, TimerMode::Once),
+        }
+    }
+}
+
+/// Set containing every registered behavior system. Runs in `FixedUpdate`
+/// as part of `GameplaySet`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnemyBehaviorSet;
+
+pub trait EnemyBehaviorAppExt {
+    /// Register `behavior` together with the systems that drive it.
+    ///
+    /// The systems should only act on enemies whose `EnemyBehavior` equals
+    /// `behavior`; enemies with other behaviors belong to other systems.
+    fn register_enemy_behavior<M>(
+        &mut self,
+        behavior: EnemyBehavior,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self;
+}
+
+impl EnemyBehaviorAppExt for App {
+    fn register_enemy_behavior<M>(
+        &mut self,
+        behavior: EnemyBehavior,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        let mut registry = self
+            .world_mut()
+            .get_resource_or_insert_with(EnemyBehaviorRegistry::default);
+        if !registry.behaviors.insert(behavior) {
+            warn!("Enemy behavior {:?} registered more than once", behavior.0);
+        }
+        self.add_systems(FixedUpdate, systems.in_set(EnemyBehaviorSet))
+    }
+}
+
+pub struct BehaviorPlugin;
+
+impl Plugin for BehaviorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyBehaviorRegistry>()
+            .configure_sets(FixedUpdate, EnemyBehaviorSet.in_set(GameplaySet))
+            .add_systems(FixedUpdate, warn_unregistered_behaviors)
+            .add_systems(
+                FixedUpdate,
+                apply_slows.after(EnemyBehaviorSet).in_set(GameplaySet),
+            );
+    }
+}
+
+/// Enemies with an unknown behavior would silently stand still, so call them out.
+fn warn_unregistered_behaviors(
+    registry: Res<EnemyBehaviorRegistry>,
+    query: Query<&EnemyBehavior, Added<EnemyBehavior>>,
+) {
+    for behavior in query.iter() {
+        if !registry.contains(*behavior) {
+            warn!("Enemy spawned with unregistered behavior {:?}", behavior.0);
+        }
+    }
+}
+
+fn apply_slows(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Slowed, &mut Velocity)>,
+) {
+    for (entity, mut slowed, mut velocity) in query.iter_mut() {
+        if slowed.timer.tick(time.delta()).finished() {
+            commands.entity(entity).remove::<Slowed>();
+            continue;
+        }
+        velocity.linvel *= slowed.factor;
+    }
+}
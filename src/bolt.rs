@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::prelude::Velocity as BodyVelocity;
+
+use crate::{
+    movement::Facing,
+    player::Player,
+    projectile::{ProjectileModifiers, ProjectileProperties},
+    targeting::{Targeting, TargetingStrategy},
+    weapon::{
+        spawn_weapon, spread, Damage, Projectile, StatChange, WeaponAppExt, WeaponCooldown,
+        WeaponDefinition, WeaponId, WeaponStats,
+    },
+};
+
+const BOLT_SPEED: f32 = 500.0;
+const BOLT_SIZE: f32 = 10.0;
+const BOLT_RANGE: f32 = 450.0;
+// Angle between bolts of the same volley.
+const VOLLEY_SPREAD: f32 = 0.15;
+const BOLT_PROPERTIES: ProjectileProperties = ProjectileProperties {
+    lifetime: 3.0,
+    range: BOLT_RANGE * 1.5,
+    pierce: 1,
+    bounces: 0,
+    homing: 0.0,
+};
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct BoltConfig {
+    pub targeting: TargetingStrategy,
+}
+
+/// Marks the bolt weapon's `WeaponCooldown`.
+#[derive(Component, Default)]
+pub struct BoltLauncher;
+
+pub struct BoltPlugin;
+
+impl Plugin for BoltPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BoltConfig>().register_weapon(
+            WeaponDefinition {
+                id: WeaponId::BOLT,
+                name: "Magic Bolt",
+                amount_label: "bolt",
+                base_stats: WeaponStats {
+                    damage: 8.0,
+                    cooldown: 1.2,
+                    ..default()
+                },
+                levels: &[
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Cooldown(-0.15)],
+                    &[StatChange::Damage(4.0)],
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Cooldown(-0.15)],
+                    &[StatChange::Damage(4.0)],
+                    &[StatChange::Amount(1)],
+                ],
+                spawn: spawn_weapon::<BoltLauncher>,
+            },
+            fire_bolts,
+        );
+    }
+}
+
+fn fire_bolts(
+    mut commands: Commands,
+    time: Res<Time>,
+    config: Res<BoltConfig>,
+    modifiers: Res<ProjectileModifiers>,
+    mut targeting: Targeting,
+    player_query: Query<(&Transform, &Facing), With<Player>>,
+    mut launcher_query: Query<(&Parent, &mut WeaponCooldown), With<BoltLauncher>>,
+) {
+    for (parent, mut cooldown) in launcher_query.iter_mut() {
+        let Ok((player_transform, facing)) = player_query.get(parent.get()) else {
+            continue;
+        };
+        if !cooldown.tick(time.delta()) {
+            continue;
+        }
+
+        let origin = player_transform.translation.truncate();
+        let Some(direction) = targeting.aim(config.targeting, origin, BOLT_RANGE, facing.0)
+        else {
+            continue;
+        };
+
+        let stats = cooldown.stats();
+        let properties = modifiers.apply(BOLT_PROPERTIES);
+        for direction in spread(direction, stats.amount, VOLLEY_SPREAD) {
+            let velocity = direction * BOLT_SPEED * stats.speed;
+            let bolt = spawn_bolt(&mut commands, origin, velocity, stats.damage, BOLT_SIZE * stats.area);
+            properties.insert(&mut commands.entity(bolt), origin);
+        }
+    }
+}
+
+fn spawn_bolt(
+    commands: &mut Commands,
+    position: Vec2,
+    velocity: Vec2,
+    damage: f32,
+    size: f32,
+) -> Entity {
+    commands
+        .spawn(Projectile)
+        .insert(Damage(damage))
+        .insert(SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.7, 0.4, 1.0),
+                custom_size: Some(Vec2::new(size, size)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 1.0),
+            ..default()
+        })
+        .insert(RigidBody::KinematicVelocityBased)
+        .insert(BodyVelocity::linear(velocity))
+        .insert(Collider::ball(size / 2.0))
+        .insert(Sensor)
+        .id()
+}
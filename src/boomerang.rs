@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    combat::{DamageEvent, DamageKind},
+    enemy::Enemy,
+    movement::Facing,
+    player::Player,
+    projectile::Lifetime,
+    targeting::{Targeting, TargetingStrategy},
+    weapon::{
+        spawn_weapon, spread, StatChange, WeaponAppExt, WeaponCooldown, WeaponDefinition,
+        WeaponId, WeaponStats,
+    },
+};
+
+const BOOMERANG_SIZE: f32 = 18.0;
+const THROW_SPEED: f32 = 600.0;
+// Units per second squared, on the way out and back.
+const DECELERATION: f32 = 900.0;
+const MAX_RETURN_SPEED: f32 = 800.0;
+const TARGET_RANGE: f32 = 350.0;
+const CATCH_DISTANCE: f32 = 20.0;
+// Angle between boomerangs of the same throw.
+const THROW_SPREAD: f32 = 0.3;
+// Safety net in case the boomerang can never reach the player.
+const MAX_FLIGHT_TIME: f32 = 6.0;
+
+/// Marks the boomerang weapon's `WeaponCooldown`.
+#[derive(Component, Default)]
+pub struct BoomerangThrower;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Leg {
+    Outbound,
+    Returning,
+}
+
+/// A thrown boomerang heading out along `direction`, then back to `owner`.
+#[derive(Component)]
+pub struct Boomerang {
+    owner: Entity,
+    direction: Vec2,
+    speed: f32,
+    leg: Leg,
+    damage: f32,
+}
+
+/// Enemies already hit during the current leg.
+#[derive(Component, Default)]
+pub struct HitTracker {
+    hit: Vec<Entity>,
+}
+
+pub struct BoomerangPlugin;
+
+impl Plugin for BoomerangPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_weapon(
+            WeaponDefinition {
+                id: WeaponId::BOOMERANG,
+                name: "Boomerang",
+                amount_label: "boomerang",
+                base_stats: WeaponStats {
+                    damage: 10.0,
+                    cooldown: 2.0,
+                    ..default()
+                },
+                levels: &[
+                    &[StatChange::Damage(4.0)],
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Speed(0.2)],
+                    &[StatChange::Damage(4.0)],
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Damage(4.0)],
+                    &[StatChange::Speed(0.2)],
+                ],
+                spawn: spawn_weapon::<BoomerangThrower>,
+            },
+            (throw_boomerangs, fly_boomerangs, boomerang_hits).chain(),
+        );
+    }
+}
+
+fn throw_boomerangs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut targeting: Targeting,
+    player_query: Query<(&Transform, &Facing), With<Player>>,
+    mut thrower_query: Query<(&Parent, &mut WeaponCooldown), With<BoomerangThrower>>,
+) {
+    for (parent, mut cooldown) in thrower_query.iter_mut() {
+        let Ok((player_transform, facing)) = player_query.get(parent.get()) else {
+            continue;
+        };
+        if !cooldown.tick(time.delta()) {
+            continue;
+        }
+
+        let stats = cooldown.stats();
+        let origin = player_transform.translation.truncate();
+        let direction = targeting
+            .aim(TargetingStrategy::Nearest, origin, TARGET_RANGE, facing.0)
+            .unwrap_or(facing.0);
+        for direction in spread(direction, stats.amount, THROW_SPREAD) {
+            spawn_boomerang(
+                &mut commands,
+                Boomerang {
+                    owner: parent.get(),
+                    direction,
+                    speed: THROW_SPEED * stats.speed,
+                    leg: Leg::Outbound,
+                    damage: stats.damage,
+                },
+                origin,
+                BOOMERANG_SIZE * stats.area,
+            );
+        }
+    }
+}
+
+fn spawn_boomerang(commands: &mut Commands, boomerang: Boomerang, position: Vec2, size: f32) {
+    commands
+        .spawn(boomerang)
+        .insert(HitTracker::default())
+        .insert(SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.9, 0.6, 0.2),
+                custom_size: Some(Vec2::new(size, size)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 1.0),
+            ..default()
+        })
+        .insert(RigidBody::KinematicPositionBased)
+        .insert(Collider::ball(size / 2.0))
+        .insert(Sensor)
+        .insert(Lifetime(Timer::from_seconds(MAX_FLIGHT_TIME, TimerMode::Once)));
+}
+
+/// Decelerate on the way out, then accelerate back towards the owner's current position.
+fn fly_boomerangs(
+    mut commands: Commands,
+    time: Res<Time>,
+    owner_query: Query<&Transform, (With<Player>, Without<Boomerang>)>,
+    mut boomerang_query: Query<(Entity, &mut Boomerang, &mut HitTracker, &mut Transform)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut boomerang, mut tracker, mut transform) in boomerang_query.iter_mut() {
+        let Ok(owner_transform) = owner_query.get(boomerang.owner) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        let position = transform.translation.truncate();
+
+        match boomerang.leg {
+            Leg::Outbound => {
+                boomerang.speed -= DECELERATION * delta;
+                if boomerang.speed <= 0.0 {
+                    boomerang.speed = 0.0;
+                    boomerang.leg = Leg::Returning;
+                    tracker.hit.clear();
+                }
+            }
+            Leg::Returning => {
+                let to_owner = owner_transform.translation.truncate() - position;
+                if to_owner.length() < CATCH_DISTANCE {
+                    commands.entity(entity).despawn_recursive();
+                    continue;
+                }
+                boomerang.direction = to_owner.normalize();
+                boomerang.speed = (boomerang.speed + DECELERATION * delta).min(MAX_RETURN_SPEED);
+            }
+        }
+
+        let step = boomerang.direction * boomerang.speed * delta;
+        transform.translation.x += step.x;
+        transform.translation.y += step.y;
+    }
+}
+
+/// Damage enemies the boomerang touches, at most once per enemy per leg. Uses
+/// the current intersections rather than `CollisionEvent::Started`, so enemies
+/// still touching the boomerang when it turns around are hit again on the way back.
+fn boomerang_hits(
+    rapier_context: Res<RapierContext>,
+    mut damage_events: EventWriter<DamageEvent>,
+    enemy_query: Query<(), With<Enemy>>,
+    mut boomerang_query: Query<(Entity, &Boomerang, &mut HitTracker)>,
+) {
+    for (entity, boomerang, mut tracker) in boomerang_query.iter_mut() {
+        for (a, b, intersecting) in rapier_context.intersection_pairs_with(entity) {
+            let other = if a == entity { b } else { a };
+            if !intersecting || !enemy_query.contains(other) || tracker.hit.contains(&other) {
+                continue;
+            }
+            tracker.hit.push(other);
+            damage_events.send(DamageEvent {
+                target: other,
+                amount: boomerang.damage,
+                kind: DamageKind::Physical,
+            });
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+
+use crate::{
+    combat::{DamageEvent, DamageKind},
+    player::Player,
+    projectile::Lifetime,
+    targeting::{Targeting, TargetingStrategy},
+    weapon::{
+        spawn_weapon, StatChange, WeaponAppExt, WeaponCooldown, WeaponDefinition, WeaponId,
+        WeaponStats,
+    },
+};
+
+const STRIKE_RANGE: f32 = 400.0;
+const JUMP_RANGE: f32 = 150.0;
+// Each jump deals this fraction of the previous one's damage.
+const JUMP_FALLOFF: f32 = 0.75;
+const SEGMENT_WIDTH: f32 = 4.0;
+const SEGMENT_DURATION: f32 = 0.15;
+
+/// Marks the chain lightning weapon's `WeaponCooldown`.
+#[derive(Component, Default)]
+pub struct ChainLightningCaster;
+
+pub struct ChainLightningPlugin;
+
+impl Plugin for ChainLightningPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_weapon(
+            WeaponDefinition {
+                id: WeaponId::CHAIN_LIGHTNING,
+                name: "Chain Lightning",
+                amount_label: "jump",
+                base_stats: WeaponStats {
+                    damage: 15.0,
+                    cooldown: 2.0,
+                    // Jumps after the first strike.
+                    amount: 2,
+                    ..default()
+                },
+                levels: &[
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Damage(5.0)],
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Area(0.25)],
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Damage(5.0)],
+                    &[StatChange::Amount(1)],
+                ],
+                spawn: spawn_weapon::<ChainLightningCaster>,
+            },
+            cast_chain_lightning,
+        );
+    }
+}
+
+fn cast_chain_lightning(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut targeting: Targeting,
+    mut damage_events: EventWriter<DamageEvent>,
+    player_query: Query<&Transform, With<Player>>,
+    mut caster_query: Query<(&Parent, &mut WeaponCooldown), With<ChainLightningCaster>>,
+) {
+    for (parent, mut cooldown) in caster_query.iter_mut() {
+        let Ok(player_transform) = player_query.get(parent.get()) else {
+            continue;
+        };
+        if !cooldown.tick(time.delta()) {
+            continue;
+        }
+
+        let stats = cooldown.stats();
+        let origin = player_transform.translation.truncate();
+        let Some(first) = targeting.pick(TargetingStrategy::RandomInRange, origin, STRIKE_RANGE)
+        else {
+            continue;
+        };
+
+        let mut hit = vec![first.entity];
+        let mut from = origin;
+        let mut target = first;
+        let mut damage = stats.damage;
+        loop {
+            spawn_segment(&mut commands, from, target.position);
+            damage_events.send(DamageEvent {
+                target: target.entity,
+                amount: damage,
+                kind: DamageKind::Lightning,
+            });
+
+            if hit.len() > stats.amount as usize {
+                break;
+            }
+            let Some(next) = targeting.nearest_excluding(target.position, JUMP_RANGE * stats.area, &hit)
+            else {
+                break;
+            };
+            hit.push(next.entity);
+            from = target.position;
+            target = next;
+            damage *= JUMP_FALLOFF;
+        }
+    }
+}
+
+/// Short-lived line sprite from `from` to `to`.
+fn spawn_segment(commands: &mut Commands, from: Vec2, to: Vec2) {
+    let delta = to - from;
+    let midpoint = (from + to) / 2.0;
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.8, 0.9, 1.0),
+                custom_size: Some(Vec2::new(delta.length(), SEGMENT_WIDTH)),
+                ..default()
+            },
+            transform: Transform::from_xyz(midpoint.x, midpoint.y, 2.0)
+                .with_rotation(Quat::from_rotation_z(delta.to_angle())),
+            ..default()
+        })
+        .insert(Lifetime(Timer::from_seconds(SEGMENT_DURATION, TimerMode::Once)));
+}
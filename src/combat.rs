@@ -2,40 +2,71 @@ use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::Rng;
 
-use crate::{ 
+use crate::{
+    animation::{spawn_death_animation, SpriteAnimation},
+    behavior::Slowed,
     components::Health,
-    enemy::Enemy,
+    enemy::{spawn_split_pieces, CurrentEnemyStats, Enemy, EnemyType, SpawnBudget, SplitOnDeath},
     experience::{spawn_experience_gem, PlayerStats},
-    loot::spawn_loot_drop,
-    weapon::Projectile,
+    loot::{spawn_loot_drop, LootType},
+    player::Player,
+    projectile::{resolve_projectile_hits, ProjectileHit},
+    rng::{GameRng, RngStream},
+    weapon::{Projectile, WeaponSet},
+    GameState, GameplaySet,
 };
 
-const PROJECTILE_DAMAGE: f32 = 10.0;
 const LOOT_DROP_CHANCE: f32 = 0.3; // 30%
+const GOLD_DROP_CHANCE: f32 = 0.5; // 50%
+// Keeps the coin from landing exactly on top of the gem.
+const GOLD_DROP_OFFSET: Vec2 = Vec2::new(12.0, 0.0);
+
+/// Where a hit came from, so effects and resistances can tell them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DamageKind {
+    Physical,
+    Lightning,
+    Explosion,
+}
+
+/// Damage dealt to an enemy by the player's weapons. The player's damage
+/// multiplier is applied when the event is handled.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct DamageEvent {
+    pub target: Entity,
+    pub amount: f32,
+    pub kind: DamageKind,
+}
 
 pub struct CombatPlugin;
 
 impl Plugin for CombatPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
+        app.add_event::<DamageEvent>().add_systems(
+            FixedUpdate,
             (
                 handle_collisions,
-                check_death.after(handle_collisions),
-            ),
+                handle_enemy_contact,
+                apply_damage.after(resolve_projectile_hits).after(WeaponSet),
+                check_player_death.after(handle_enemy_contact),
+                check_death.after(apply_damage),
+            )
+                .in_set(GameplaySet),
         );
     }
 }
 
-fn handle_collisions(
+/// Turn projectile/enemy contacts into `ProjectileHit`s. Damage is dealt by
+/// `resolve_projectile_hits`, which knows whether the projectile is spent.
+pub(crate) fn handle_collisions(
     mut collision_events: EventReader<CollisionEvent>,
-    mut enemy_query: Query<&mut Health, With<Enemy>>,
-    projectile_query: Query<Entity, With<Projectile>>,
-    player_stats: Res<PlayerStats>,
+    mut hits: EventWriter<ProjectileHit>,
+    enemy_query: Query<(), With<Enemy>>,
+    projectile_query: Query<(), With<Projectile>>,
 ) {
     for event in collision_events.read() {
         if let CollisionEvent::Started(entity1, entity2, _) = event {
-            let (_projectile_entity, enemy_entity) =
+            let (projectile_entity, enemy_entity) =
                 if projectile_query.get(*entity1).is_ok() && enemy_query.get(*entity2).is_ok() {
                     (*entity1, *entity2)
                 } else if projectile_query.get(*entity2).is_ok() && enemy_query.get(*entity1).is_ok()
@@ -45,28 +76,127 @@ fn handle_collisions(
                     continue;
                 };
 
-            if let Ok(mut health) = enemy_query.get_mut(enemy_entity) {
-                health.value -= PROJECTILE_DAMAGE * player_stats.damage_multiplier;
+            hits.send(ProjectileHit {
+                projectile: projectile_entity,
+                enemy: enemy_entity,
+            });
+        }
+    }
+}
+
+fn apply_damage(
+    mut damage_events: EventReader<DamageEvent>,
+    mut enemy_query: Query<&mut Health, With<Enemy>>,
+    player_stats: Res<PlayerStats>,
+) {
+    for event in damage_events.read() {
+        if let Ok(mut health) = enemy_query.get_mut(event.target) {
+            health.value -= event.amount * player_stats.damage_multiplier;
+        }
+    }
+}
+
+/// Enemies touching the player deal their contact damage once per touch.
+fn handle_enemy_contact(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut player_query: Query<(Entity, &mut Health), (With<Player>, Without<Enemy>)>,
+    enemy_query: Query<&CurrentEnemyStats, With<Enemy>>,
+) {
+    let Ok((player_entity, mut player_health)) = player_query.get_single_mut() else {
+        return;
+    };
+
+    for event in collision_events.read() {
+        if let CollisionEvent::Started(entity1, entity2, _) = event {
+            let enemy_entity = if *entity1 == player_entity {
+                *entity2
+            } else if *entity2 == player_entity {
+                *entity1
+            } else {
+                continue;
+            };
+
+            if let Ok(stats) = enemy_query.get(enemy_entity) {
+                player_health.value -= stats.damage;
             }
         }
     }
 }
 
+fn check_player_death(
+    player_query: Query<&Health, With<Player>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if let Ok(health) = player_query.get_single() {
+        if health.value <= 0.0 {
+            next_state.set(GameState::GameOver);
+        }
+    }
+}
+
 fn check_death(
     mut commands: Commands,
-    query: Query<(Entity, &Transform, &Health), With<Enemy>>,
+    mut game_rng: ResMut<GameRng>,
+    mut budget: ResMut<SpawnBudget>,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &Health,
+            &EnemyType,
+            &CurrentEnemyStats,
+            Option<&SplitOnDeath>,
+            Option<&Slowed>,
+            // Only enemies whose sheet has loaded have an atlas to play the death clip from.
+            Option<(&SpriteAnimation, &Sprite, &TextureAtlas)>,
+        ),
+        With<Enemy>,
+    >,
 ) {
-    let mut rng = rand::thread_rng();
-    for (entity, transform, health) in query.iter() {
+    let rng = game_rng.stream(RngStream::Loot);
+    for (entity, transform, health, enemy_type, stats, split, slowed, animation) in query.iter() {
         if health.value <= 0.0 {
-            commands.entity(entity).despawn();
+            commands.entity(entity).despawn_recursive();
+            budget.release();
             let position = transform.translation.truncate();
 
+            if let Some((animation, sprite, _)) = animation {
+                spawn_death_animation(&mut commands, animation.sheet, sprite.clone(), *transform);
+            }
+
+            if let Some(split) = split.filter(|split| split.can_split(stats.size)) {
+                let pieces = spawn_split_pieces(
+                    &mut commands,
+                    &mut budget,
+                    *enemy_type,
+                    stats,
+                    health,
+                    split,
+                    position,
+                );
+                // Status effects carry over to the pieces. Orders tied to the parent
+                // (stampede runs, summoned minions) are left out; slimes never have them.
+                if let Some(slowed) = slowed {
+                    for piece in pieces {
+                        commands.entity(piece).insert(slowed.clone());
+                    }
+                }
+            }
+
             if rng.gen_range(0.0..1.0) < LOOT_DROP_CHANCE {
-                spawn_loot_drop(&mut commands, position);
+                spawn_loot_drop(&mut commands, position, LootType::HealthPack);
             } else {
                 spawn_experience_gem(&mut commands, position);
             }
+
+            let gold = enemy_type.gold_drop();
+            if gold > 0 && rng.gen_range(0.0..1.0) < GOLD_DROP_CHANCE {
+                spawn_loot_drop(
+                    &mut commands,
+                    position + GOLD_DROP_OFFSET,
+                    LootType::Gold { amount: gold },
+                );
+            }
         }
     }
 }
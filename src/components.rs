@@ -3,4 +3,24 @@ use bevy::prelude::Component;
 #[derive(Component, Debug)]
 pub struct Health {
     pub value: f32,
+    pub max: f32,
+}
+
+impl Health {
+    /// Full health with the given maximum.
+    pub fn new(max: f32) -> Self {
+        Self { value: max, max }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.value / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.value >= self.max
+    }
 }
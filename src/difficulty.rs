@@ -0,0 +1,136 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use bevy::time::Stopwatch;
+
+use crate::{enemy::EnemyStats, GameplaySet};
+
+/// Player-facing difficulty choice. Each setting maps to a `DifficultyCurve`.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DifficultySetting {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+/// How enemies get tougher over the course of a run.
+///
+/// Growth rates are linear fractions per minute of run time, e.g. a
+/// `health_per_minute` of 0.1 means enemies spawned at minute 10 have twice
+/// their base health.
+#[derive(Resource, Debug, Clone)]
+pub struct DifficultyCurve {
+    pub health_per_minute: f32,
+    pub speed_per_minute: f32,
+    pub damage_per_minute: f32,
+    /// Cap on the speed multiplier so late enemies stay dodgeable.
+    pub max_speed_scale: f32,
+    /// Fraction the spawn interval shrinks by each minute (compounding).
+    pub spawn_interval_decay: f32,
+    pub min_spawn_interval: f32,
+}
+
+impl DifficultyCurve {
+    pub fn for_setting(setting: DifficultySetting) -> Self {
+        match setting {
+            DifficultySetting::Easy => Self {
+                health_per_minute: 0.05,
+                speed_per_minute: 0.01,
+                damage_per_minute: 0.03,
+                max_speed_scale: 1.3,
+                spawn_interval_decay: 0.03,
+                min_spawn_interval: 0.2,
+            },
+            DifficultySetting::Normal => Self {
+                health_per_minute: 0.1,
+                speed_per_minute: 0.02,
+                damage_per_minute: 0.05,
+                max_speed_scale: 1.5,
+                spawn_interval_decay: 0.05,
+                min_spawn_interval: 0.1,
+            },
+            DifficultySetting::Hard => Self {
+                health_per_minute: 0.2,
+                speed_per_minute: 0.03,
+                damage_per_minute: 0.1,
+                max_speed_scale: 1.8,
+                spawn_interval_decay: 0.08,
+                min_spawn_interval: 0.05,
+            },
+        }
+    }
+
+    /// Base enemy stats scaled for an enemy spawned `minutes` into the run.
+    pub fn scale_stats(&self, stats: EnemyStats, minutes: f32) -> EnemyStats {
+        EnemyStats {
+            health: stats.health * (1.0 + self.health_per_minute * minutes),
+            speed: stats.speed * (1.0 + self.speed_per_minute * minutes).min(self.max_speed_scale),
+            damage: stats.damage * (1.0 + self.damage_per_minute * minutes),
+            ..stats
+        }
+    }
+
+    pub fn spawn_interval(&self, base: f32, minutes: f32) -> f32 {
+        (base * (1.0 - self.spawn_interval_decay).powf(minutes)).max(self.min_spawn_interval)
+    }
+}
+
+impl Default for DifficultyCurve {
+    fn default() -> Self {
+        Self::for_setting(DifficultySetting::default())
+    }
+}
+
+/// Time spent in gameplay this run. Does not advance while paused or in menus.
+#[derive(Resource, Default)]
+pub struct RunTime(pub Stopwatch);
+
+impl RunTime {
+    pub fn minutes(&self) -> f32 {
+        self.0.elapsed_secs() / 60.0
+    }
+}
+
+/// The difficulty curve evaluated at the current run time, for systems that spawn enemies.
+#[derive(SystemParam)]
+pub struct Difficulty<'w> {
+    curve: Res<'w, DifficultyCurve>,
+    run_time: Res<'w, RunTime>,
+}
+
+impl Difficulty<'_> {
+    /// Base enemy stats scaled for an enemy spawned now.
+    pub fn scale_stats(&self, stats: EnemyStats) -> EnemyStats {
+        self.curve.scale_stats(stats, self.run_time.minutes())
+    }
+
+    pub fn spawn_interval(&self, base: f32) -> f32 {
+        self.curve.spawn_interval(base, self.run_time.minutes())
+    }
+}
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DifficultySetting>()
+            .init_resource::<DifficultyCurve>()
+            .init_resource::<RunTime>()
+            .add_systems(Update, apply_difficulty_setting)
+            .add_systems(FixedUpdate, tick_run_time.in_set(GameplaySet));
+    }
+}
+
+/// Swap in the matching curve whenever the difficulty setting changes.
+fn apply_difficulty_setting(
+    setting: Res<DifficultySetting>,
+    mut curve: ResMut<DifficultyCurve>,
+) {
+    if setting.is_changed() {
+        *curve = DifficultyCurve::for_setting(*setting);
+    }
+}
+
+fn tick_run_time(time: Res<Time>, mut run_time: ResMut<RunTime>) {
+    run_time.0.tick(time.delta());
+}
@@ -1,10 +1,30 @@
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 use rand::Rng;
 
-use crate::{components::Health, player::Player};
+use crate::{
+    animation::{SpriteAnimation, SpriteSheet},
+    behavior::{EnemyBehavior, EnemyBehaviorAppExt, EnemyBehaviorSet},
+    components::Health,
+    difficulty::Difficulty,
+    flow_field::FlowField,
+    formation::Stampede,
+    player::Player,
+    rng::{GameRng, RngStream},
+    simulation::InterpolatedPosition,
+    spawn_area::{SpawnPlacement, SpawnView},
+    GameplaySet,
+};
 
 const ENEMY_SPAWN_TIME: f32 = 0.5;
+const DEFAULT_MAX_ALIVE: usize = 500;
+const DEFAULT_RECYCLE_DISTANCE: f32 = 600.0;
+// Each split piece is this fraction of its parent's size.
+const SPLIT_SIZE_SCALE: f32 = 0.65;
+const SPLIT_HEALTH_SCALE: f32 = 0.5;
+const SPLIT_SPEED_SCALE: f32 = 1.3;
 
 #[derive(Component)]
 pub struct Enemy;
@@ -12,39 +32,132 @@ pub struct Enemy;
 #[derive(Component)]
 pub struct CurrentEnemyStats {
     pub speed: f32,
+    pub size: f32,
+    /// Contact damage dealt to the player.
+    pub damage: f32,
+}
+
+/// Divides into `pieces` smaller, faster copies on death until the pieces
+/// would be smaller than `min_size`.
+#[derive(Component)]
+pub struct SplitOnDeath {
+    pub pieces: u32,
+    pub min_size: f32,
 }
 
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EnemyType {
     Grunt,
     Tank,
+    Slime,
+    Summoner,
+    /// Weak enemy only ever created by a summoner.
+    Minion,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub struct EnemyStats {
     pub health: f32,
     pub speed: f32,
     pub size: f32,
+    pub damage: f32,
     pub color: Color,
+    pub behavior: EnemyBehavior,
+    pub sprite_sheet: SpriteSheet,
 }
 
 impl EnemyType {
-    fn get_stats(&self) -> EnemyStats {
+    pub fn get_stats(&self) -> EnemyStats {
         match self {
             EnemyType::Grunt => EnemyStats {
                 health: 50.0,
                 speed: 250.0,
                 size: 40.0,
+                damage: 10.0,
                 color: Color::srgb(0.8, 0.2, 0.2), // Lighter red
+                behavior: EnemyBehavior::CHASE,
+                sprite_sheet: SpriteSheet::standard("sprites/grunt.png", 32, 6, 250.0),
             },
             EnemyType::Tank => EnemyStats {
                 health: 200.0,
                 speed: 150.0,
                 size: 75.0,
+                damage: 25.0,
                 color: Color::srgb(0.6, 0.0, 0.0), // Darker red
+                behavior: EnemyBehavior::CHASE,
+                sprite_sheet: SpriteSheet::standard("sprites/tank.png", 48, 6, 150.0),
             },
+            EnemyType::Slime => EnemyStats {
+                health: 120.0,
+                speed: 120.0,
+                size: 64.0,
+                damage: 15.0,
+                color: Color::srgb(0.3, 0.8, 0.3), // Green
+                behavior: EnemyBehavior::CHASE,
+                sprite_sheet: SpriteSheet::standard("sprites/slime.png", 32, 8, 120.0),
+            },
+            EnemyType::Summoner => EnemyStats {
+                health: 150.0,
+                speed: 110.0,
+                size: 50.0,
+                damage: 10.0,
+                color: Color::srgb(0.5, 0.2, 0.7), // Purple
+                behavior: EnemyBehavior::SUMMON,
+                sprite_sheet: SpriteSheet::standard("sprites/summoner.png", 32, 6, 110.0),
+            },
+            EnemyType::Minion => EnemyStats {
+                health: 15.0,
+                speed: 280.0,
+                size: 20.0,
+                damage: 5.0,
+                color: Color::srgb(0.75, 0.5, 0.9), // Pale purple
+                behavior: EnemyBehavior::CHASE,
+                sprite_sheet: SpriteSheet::standard("sprites/minion.png", 16, 4, 280.0),
+            },
+        }
+    }
+
+    /// Gold dropped when an enemy of this type dies.
+    pub fn gold_drop(&self) -> u32 {
+        match self {
+            EnemyType::Grunt => 1,
+            EnemyType::Tank => 5,
+            EnemyType::Slime => 2,
+            EnemyType::Summoner => 8,
+            EnemyType::Minion => 0,
+        }
+    }
+
+    fn split_on_death(&self) -> Option<SplitOnDeath> {
+        match self {
+            EnemyType::Slime => Some(SplitOnDeath {
+                pieces: 2,
+                min_size: 20.0,
+            }),
+            _ => None,
+        }
+    }
+}
+
+impl EnemyStats {
+    /// Stats of one piece when an enemy with these stats splits.
+    pub fn split(&self) -> EnemyStats {
+        EnemyStats {
+            health: self.health * SPLIT_HEALTH_SCALE,
+            speed: self.speed * SPLIT_SPEED_SCALE,
+            size: self.size * SPLIT_SIZE_SCALE,
+            ..*self
         }
     }
 }
 
+impl SplitOnDeath {
+    /// Whether an enemy of `size` is still big enough to split.
+    pub fn can_split(&self, size: f32) -> bool {
+        size * SPLIT_SIZE_SCALE >= self.min_size
+    }
+}
+
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
@@ -53,73 +166,248 @@ impl Plugin for EnemyPlugin {
             ENEMY_SPAWN_TIME,
             TimerMode::Repeating,
         )))
-        .add_systems(Update, (spawn_enemies, enemy_movement));
+        .init_resource::<EnemyPopulation>()
+        .init_resource::<SpawnBudget>()
+        .init_resource::<SpawnPlacement>()
+        .add_systems(FixedFirst, count_spawn_budget)
+        .add_systems(
+            FixedUpdate,
+            (spawn_enemies, recycle_distant_enemies)
+                .before(EnemyBehaviorSet)
+                .in_set(GameplaySet),
+        )
+        .register_enemy_behavior(EnemyBehavior::CHASE, chase_behavior);
     }
 }
 
 #[derive(Resource)]
 struct EnemySpawnTimer(Timer);
 
+/// Limits on the live enemy population.
+#[derive(Resource, Debug, Clone)]
+pub struct EnemyPopulation {
+    /// No new enemies are spawned while this many are alive.
+    pub max_alive: usize,
+    /// Enemies further than this past the off-screen spawn ring are moved back
+    /// to just outside the view. Measured from the ring rather than the player,
+    /// so recycled enemies never land beyond it, however large the view is.
+    pub recycle_distance: f32,
+}
+
+impl Default for EnemyPopulation {
+    fn default() -> Self {
+        Self {
+            max_alive: DEFAULT_MAX_ALIVE,
+            recycle_distance: DEFAULT_RECYCLE_DISTANCE,
+        }
+    }
+}
+
+/// How many more enemies may be spawned this tick before reaching
+/// `EnemyPopulation::max_alive`. Recounted at the start of every tick and
+/// shared by all spawners, so together they can't overshoot the cap.
+#[derive(Resource, Default)]
+pub struct SpawnBudget {
+    remaining: usize,
+}
+
+impl SpawnBudget {
+    /// Count an enemy despawned this tick, freeing its slot.
+    pub fn release(&mut self) {
+        self.remaining += 1;
+    }
+
+    fn take(&mut self) -> bool {
+        if self.remaining == 0 {
+            return false;
+        }
+        self.remaining -= 1;
+        true
+    }
+}
+
+fn count_spawn_budget(
+    population: Res<EnemyPopulation>,
+    mut budget: ResMut<SpawnBudget>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    budget.remaining = population.max_alive.saturating_sub(enemy_query.iter().count());
+}
+
 fn spawn_enemies(
     mut commands: Commands,
     time: Res<Time>,
+    difficulty: Difficulty,
+    mut game_rng: ResMut<GameRng>,
     mut spawn_timer: ResMut<EnemySpawnTimer>,
-    windows: Query<&Window>,
-    player_query: Query<&Transform, With<Player>>,
+    mut budget: ResMut<SpawnBudget>,
+    spawn_view: SpawnView,
 ) {
+    let interval = difficulty.spawn_interval(ENEMY_SPAWN_TIME);
+    spawn_timer.0.set_duration(Duration::from_secs_f32(interval));
     spawn_timer.0.tick(time.delta());
     if !spawn_timer.0.just_finished() {
         return;
     }
+    if budget.remaining == 0 {
+        return;
+    }
 
-    let window = windows.single();
-    let player_transform = player_query.single();
-    let mut rng = rand::thread_rng();
+    let rng = game_rng.stream(RngStream::Spawning);
 
-    let enemy_type = if rng.gen_bool(0.7) {
+    let roll = rng.gen_range(0.0..1.0_f32);
+    let enemy_type = if roll < 0.55 {
         EnemyType::Grunt
-    } else {
+    } else if roll < 0.8 {
         EnemyType::Tank
+    } else if roll < 0.93 {
+        EnemyType::Slime
+    } else {
+        EnemyType::Summoner
     };
-    let stats = enemy_type.get_stats();
+    let stats = difficulty.scale_stats(enemy_type.get_stats());
 
-    let spawn_angle = rng.gen_range(0.0..360.0_f32).to_radians();
-    let spawn_dist = (window.width().powi(2) + window.height().powi(2)).sqrt() / 2.0 + stats.size;
+    let Some(spawn_pos) = spawn_view.random_position(stats.size, rng) else {
+        return;
+    };
 
-    let spawn_pos = player_transform.translation.truncate()
-        + Vec2::new(spawn_angle.cos(), spawn_angle.sin()) * spawn_dist;
+    spawn_enemy(&mut commands, &mut budget, enemy_type, stats, spawn_pos);
+}
 
-    commands
-        .spawn(Enemy)
-        .insert(Health { value: stats.health })
-        .insert(CurrentEnemyStats { speed: stats.speed })
+/// Spawn a single enemy of `enemy_type` with the given (possibly modified) stats.
+/// Returns `None` without spawning once `budget` is used up.
+pub fn spawn_enemy(
+    commands: &mut Commands,
+    budget: &mut SpawnBudget,
+    enemy_type: EnemyType,
+    stats: EnemyStats,
+    position: Vec2,
+) -> Option<Entity> {
+    if !budget.take() {
+        return None;
+    }
+    let mut enemy = commands.spawn(Enemy);
+    enemy
+        .insert(enemy_type)
+        .insert(stats.behavior)
+        .insert(SpriteAnimation::new(stats.sprite_sheet))
+        .insert(Health::new(stats.health))
+        .insert(CurrentEnemyStats {
+            speed: stats.speed,
+            size: stats.size,
+            damage: stats.damage,
+        })
         .insert(SpriteBundle {
             sprite: Sprite {
                 color: stats.color,
                 custom_size: Some(Vec2::new(stats.size, stats.size)),
                 ..default()
             },
-            transform: Transform::from_xyz(spawn_pos.x, spawn_pos.y, 0.0),
+            transform: Transform::from_xyz(position.x, position.y, 0.0),
             ..default()
         })
         .insert(RigidBody::Dynamic)
+        // Keep enemies upright so their health bars don't spin with them.
+        .insert(LockedAxes::ROTATION_LOCKED)
         .insert(Collider::ball(stats.size / 2.0))
         .insert(ActiveEvents::COLLISION_EVENTS)
         .insert(Velocity::zero());
+    if let Some(split) = enemy_type.split_on_death() {
+        enemy.insert(split);
+    }
+    Some(enemy.id())
+}
+
+/// Spawn the pieces of a split enemy spread evenly around `position`.
+/// Returns the pieces so the caller can pass on the parent's status effects.
+pub fn spawn_split_pieces(
+    commands: &mut Commands,
+    budget: &mut SpawnBudget,
+    enemy_type: EnemyType,
+    parent: &CurrentEnemyStats,
+    parent_health: &Health,
+    split: &SplitOnDeath,
+    position: Vec2,
+) -> Vec<Entity> {
+    let piece_stats = EnemyStats {
+        health: parent_health.max,
+        speed: parent.speed,
+        size: parent.size,
+        damage: parent.damage,
+        ..enemy_type.get_stats()
+    }
+    .split();
+
+    (0..split.pieces)
+        .filter_map(|i| {
+            let angle = i as f32 / split.pieces as f32 * std::f32::consts::TAU;
+            let offset = Vec2::new(angle.cos(), angle.sin()) * piece_stats.size * 0.5;
+            spawn_enemy(commands, budget, enemy_type, piece_stats, position + offset)
+        })
+        .collect()
+}
+
+/// Teleport enemies the player has left far behind to just outside the view,
+/// reusing the entity instead of despawning it and spawning a fresh one.
+fn recycle_distant_enemies(
+    population: Res<EnemyPopulation>,
+    mut game_rng: ResMut<GameRng>,
+    spawn_view: SpawnView,
+    mut enemy_query: Query<
+        (
+            &mut Transform,
+            &mut Velocity,
+            &CurrentEnemyStats,
+            Option<&mut InterpolatedPosition>,
+        ),
+        (With<Enemy>, Without<Stampede>),
+    >,
+) {
+    let Some(area) = spawn_view.area() else {
+        return;
+    };
+    let center = area.center();
+    let rng = game_rng.stream(RngStream::Spawning);
+
+    for (mut transform, mut velocity, stats, interpolated) in enemy_query.iter_mut() {
+        let limit = area.ring_radius(stats.size) + population.recycle_distance;
+        if transform.translation.truncate().distance(center) <= limit {
+            continue;
+        }
+        let position = area.edge_point(spawn_view.random_direction(rng), stats.size);
+        transform.translation.x = position.x;
+        transform.translation.y = position.y;
+        *velocity = Velocity::zero();
+        if let Some(mut interpolated) = interpolated {
+            interpolated.teleport(transform.translation);
+        }
+    }
 }
 
-fn enemy_movement(
+/// Built-in `EnemyBehavior::CHASE`.
+fn chase_behavior(
+    flow_field: Res<FlowField>,
     player_query: Query<&Transform, With<Player>>,
-    mut enemy_query: Query<(&Transform, &mut Velocity, &CurrentEnemyStats), (With<Enemy>, Without<Player>)>,
+    mut enemy_query: Query<
+        (&EnemyBehavior, &Transform, &mut Velocity, &CurrentEnemyStats),
+        (With<Enemy>, Without<Player>),
+    >,
 ) {
     if player_query.is_empty() {
         return;
     }
     let player_transform = player_query.single();
 
-    for (enemy_transform, mut velocity, stats) in enemy_query.iter_mut() {
-        let direction =
-            (player_transform.translation - enemy_transform.translation).truncate().normalize_or_zero();
+    for (behavior, enemy_transform, mut velocity, stats) in enemy_query.iter_mut() {
+        if *behavior != EnemyBehavior::CHASE {
+            continue;
+        }
+        let position = enemy_transform.translation.truncate();
+        // Follow the flow field around obstacles; fall back to a straight chase when
+        // outside the field or already sharing a cell with the player.
+        let direction = flow_field.sample(position).unwrap_or_else(|| {
+            (player_transform.translation.truncate() - position).normalize_or_zero()
+        });
         velocity.linvel = direction * stats.speed;
     }
 }
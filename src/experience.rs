@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::{player::Player, GameState};
+use crate::{player::Player, GameState, GameplaySet};
 
 const GEM_SIZE: f32 = 15.0;
 const GEM_VALUE: u32 = 10;
@@ -17,7 +17,6 @@ pub struct PlayerStats {
     pub xp_to_next_level: u32,
     pub damage_multiplier: f32,
     pub speed_multiplier: f32,
-    pub orbital_count: u32,
 }
 
 impl Default for PlayerStats {
@@ -28,7 +27,6 @@ impl Default for PlayerStats {
             xp_to_next_level: INITIAL_XP_TO_NEXT_LEVEL,
             damage_multiplier: 1.0,
             speed_multiplier: 1.0,
-            orbital_count: 1,
         }
     }
 }
@@ -38,12 +36,12 @@ pub struct ExperiencePlugin;
 impl Plugin for ExperiencePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<PlayerStats>().add_systems(
-            Update,
+            FixedUpdate,
             (
                 handle_gem_collection,
                 level_up_system,
             )
-                .run_if(in_state(GameState::Gameplay)),
+                .in_set(GameplaySet),
         );
     }
 }
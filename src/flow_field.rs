@@ -0,0 +1,317 @@
+use std::collections::{HashSet, VecDeque};
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{player::Player, GameplaySet};
+
+const CELL_SIZE: f32 = 32.0;
+// Number of cells on each side of the player covered by the field.
+const GRID_HALF_EXTENT: i32 = 48;
+const GRID_SIZE: i32 = GRID_HALF_EXTENT * 2 + 1;
+const UNREACHABLE: u32 = u32::MAX;
+// Cells processed per fixed tick while rebuilding. A full rebuild touches every
+// cell twice, so this finishes in about five ticks.
+const CELLS_PER_TICK: usize = 4_000;
+
+const NEIGHBOURS: [IVec2; 8] = [
+    IVec2::new(1, 0),
+    IVec2::new(-1, 0),
+    IVec2::new(0, 1),
+    IVec2::new(0, -1),
+    IVec2::new(1, 1),
+    IVec2::new(1, -1),
+    IVec2::new(-1, 1),
+    IVec2::new(-1, -1),
+];
+
+/// Static world geometry (walls, props) that enemies have to path around.
+#[derive(Component)]
+pub struct Obstacle {
+    pub half_extents: Vec2,
+}
+
+/// Grid flow field pointing every walkable cell towards the player.
+///
+/// The grid is centered on the player and only rebuilt when the player moves
+/// into a different cell or an obstacle changes, so enemies can sample it
+/// every frame at a constant cost regardless of how many there are.
+///
+/// Obstacles are rasterized once into world cells and reused; a rebuild only
+/// shifts the existing raster and looks up the newly exposed strip. The
+/// distance pass still covers the whole grid, since moving the target changes
+/// every cost, so it is spread over several ticks while enemies keep following
+/// the previous field. That field lags the player by at most a few ticks,
+/// which is a single cell at normal movement speed.
+#[derive(Resource)]
+pub struct FlowField {
+    origin: IVec2,
+    player_cell: Option<IVec2>,
+    blocked: Vec<bool>,
+    directions: Vec<Vec2>,
+    // World cells covered by obstacles, independent of where the grid is.
+    blocked_cells: HashSet<IVec2>,
+    build: Option<FieldBuild>,
+}
+
+impl Default for FlowField {
+    fn default() -> Self {
+        let cells = (GRID_SIZE * GRID_SIZE) as usize;
+        Self {
+            origin: IVec2::ZERO,
+            player_cell: None,
+            blocked: vec![false; cells],
+            directions: vec![Vec2::ZERO; cells],
+            blocked_cells: HashSet::new(),
+            build: None,
+        }
+    }
+}
+
+fn grid_index(origin: IVec2, cell: IVec2) -> Option<usize> {
+    let local = cell - origin;
+    if local.x < 0 || local.y < 0 || local.x >= GRID_SIZE || local.y >= GRID_SIZE {
+        return None;
+    }
+    Some((local.y * GRID_SIZE + local.x) as usize)
+}
+
+impl FlowField {
+    fn world_to_cell(position: Vec2) -> IVec2 {
+        (position / CELL_SIZE).floor().as_ivec2()
+    }
+
+    /// Direction to move from `position` to get closer to the player, or `None`
+    /// if the position is outside the field, blocked, or already in the player's cell.
+    pub fn sample(&self, position: Vec2) -> Option<Vec2> {
+        let index = grid_index(self.origin, Self::world_to_cell(position))?;
+        let direction = self.directions[index];
+        if direction == Vec2::ZERO {
+            None
+        } else {
+            Some(direction)
+        }
+    }
+
+    fn rasterize_obstacles<'a>(&mut self, obstacles: impl Iterator<Item = (&'a Transform, &'a Obstacle)>) {
+        self.blocked_cells.clear();
+        for (transform, obstacle) in obstacles {
+            let center = transform.translation.truncate();
+            let min = Self::world_to_cell(center - obstacle.half_extents);
+            let max = Self::world_to_cell(center + obstacle.half_extents);
+            for y in min.y..=max.y {
+                for x in min.x..=max.x {
+                    self.blocked_cells.insert(IVec2::new(x, y));
+                }
+            }
+        }
+    }
+
+    /// Blocked cells for a grid at `origin`. Cells the current grid already covers
+    /// are copied when `reuse` is set; the rest come from the obstacle raster.
+    fn blocked_at(&self, origin: IVec2, reuse: bool) -> Vec<bool> {
+        let mut blocked = vec![false; self.blocked.len()];
+        for y in 0..GRID_SIZE {
+            for x in 0..GRID_SIZE {
+                let cell = origin + IVec2::new(x, y);
+                let previous = grid_index(self.origin, cell).filter(|_| reuse);
+                blocked[(y * GRID_SIZE + x) as usize] = match previous {
+                    Some(i) => self.blocked[i],
+                    None => self.blocked_cells.contains(&cell),
+                };
+            }
+        }
+        blocked
+    }
+
+    fn start_build(&mut self, target: IVec2, reuse_raster: bool) {
+        let origin = target - IVec2::splat(GRID_HALF_EXTENT);
+        let blocked = self.blocked_at(origin, reuse_raster && self.player_cell.is_some());
+        self.build = Some(FieldBuild::new(origin, target, blocked));
+    }
+
+    /// Advance the pending build by `budget` cells, installing it once complete.
+    fn step_build(&mut self, budget: usize) {
+        let Some(build) = self.build.as_mut() else {
+            return;
+        };
+        if !build.step(budget) {
+            return;
+        }
+        let build = self.build.take().unwrap();
+        self.origin = build.origin;
+        self.player_cell = Some(build.target);
+        self.blocked = build.blocked;
+        self.directions = build.directions;
+    }
+}
+
+/// A field being computed over several ticks.
+struct FieldBuild {
+    origin: IVec2,
+    target: IVec2,
+    blocked: Vec<bool>,
+    cost: Vec<u32>,
+    directions: Vec<Vec2>,
+    frontier: VecDeque<IVec2>,
+    // Next row of `directions` to fill once the frontier is empty.
+    next_row: i32,
+}
+
+impl FieldBuild {
+    fn new(origin: IVec2, target: IVec2, blocked: Vec<bool>) -> Self {
+        let cells = blocked.len();
+        let mut cost = vec![UNREACHABLE; cells];
+        let mut frontier = VecDeque::new();
+        if let Some(i) = grid_index(origin, target) {
+            cost[i] = 0;
+            frontier.push_back(target);
+        }
+        Self {
+            origin,
+            target,
+            blocked,
+            cost,
+            directions: vec![Vec2::ZERO; cells],
+            frontier,
+            next_row: 0,
+        }
+    }
+
+    fn is_walkable(&self, cell: IVec2) -> bool {
+        grid_index(self.origin, cell).is_some_and(|i| !self.blocked[i])
+    }
+
+    /// Process up to `budget` cells. Returns true once the field is complete.
+    fn step(&mut self, mut budget: usize) -> bool {
+        // Integration field: breadth-first distance from the player's cell.
+        while budget > 0 {
+            let Some(cell) = self.frontier.pop_front() else {
+                break;
+            };
+            budget -= 1;
+            let next_cost = self.cost[grid_index(self.origin, cell).unwrap()] + 1;
+            for offset in &NEIGHBOURS[..4] {
+                let neighbour = cell + *offset;
+                let Some(i) = grid_index(self.origin, neighbour) else {
+                    continue;
+                };
+                if self.blocked[i] || self.cost[i] <= next_cost {
+                    continue;
+                }
+                self.cost[i] = next_cost;
+                self.frontier.push_back(neighbour);
+            }
+        }
+        if !self.frontier.is_empty() {
+            return false;
+        }
+
+        // Flow field: every cell points at its cheapest neighbour. Diagonals are only
+        // taken when both adjacent sides are open so enemies don't clip wall corners.
+        while self.next_row < GRID_SIZE && budget > 0 {
+            let y = self.next_row;
+            self.next_row += 1;
+            budget = budget.saturating_sub(GRID_SIZE as usize);
+            for x in 0..GRID_SIZE {
+                let cell = self.origin + IVec2::new(x, y);
+                let index = (y * GRID_SIZE + x) as usize;
+                if self.blocked[index] || self.cost[index] == UNREACHABLE || self.cost[index] == 0 {
+                    continue;
+                }
+
+                let mut best = self.cost[index];
+                let mut best_offset = IVec2::ZERO;
+                for offset in NEIGHBOURS {
+                    if offset.x != 0
+                        && offset.y != 0
+                        && (!self.is_walkable(cell + IVec2::new(offset.x, 0))
+                            || !self.is_walkable(cell + IVec2::new(0, offset.y)))
+                    {
+                        continue;
+                    }
+                    if let Some(i) = grid_index(self.origin, cell + offset) {
+                        if self.cost[i] < best {
+                            best = self.cost[i];
+                            best_offset = offset;
+                        }
+                    }
+                }
+                self.directions[index] = best_offset.as_vec2().normalize_or_zero();
+            }
+        }
+        self.next_row >= GRID_SIZE
+    }
+}
+
+pub struct FlowFieldPlugin;
+
+impl Plugin for FlowFieldPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlowField>()
+            .add_systems(Startup, spawn_props)
+            .add_systems(FixedUpdate, update_flow_field.in_set(GameplaySet));
+    }
+}
+
+/// Spawn a static obstacle with a matching collider and flow field footprint.
+pub fn spawn_obstacle(commands: &mut Commands, position: Vec2, size: Vec2) -> Entity {
+    commands
+        .spawn(Obstacle { half_extents: size / 2.0 })
+        .insert(SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgb(0.35, 0.35, 0.4),
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, 0.0),
+            ..default()
+        })
+        .insert(RigidBody::Fixed)
+        .insert(Collider::cuboid(size.x / 2.0, size.y / 2.0))
+        .id()
+}
+
+/// Place a few props around the spawn so the arena isn't an empty plane.
+fn spawn_props(mut commands: Commands) {
+    let props = [
+        (Vec2::new(300.0, 200.0), Vec2::new(64.0, 256.0)),
+        (Vec2::new(-350.0, -150.0), Vec2::new(256.0, 64.0)),
+        (Vec2::new(-250.0, 320.0), Vec2::new(96.0, 96.0)),
+        (Vec2::new(420.0, -300.0), Vec2::new(160.0, 64.0)),
+    ];
+    for (position, size) in props {
+        spawn_obstacle(&mut commands, position, size);
+    }
+}
+
+fn update_flow_field(
+    mut flow_field: ResMut<FlowField>,
+    player_query: Query<&Transform, With<Player>>,
+    obstacle_query: Query<(&Transform, &Obstacle)>,
+    changed_obstacles: Query<(), (With<Obstacle>, Or<(Added<Obstacle>, Changed<Transform>)>)>,
+    mut removed_obstacles: RemovedComponents<Obstacle>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_cell = FlowField::world_to_cell(player_transform.translation.truncate());
+
+    let obstacles_changed =
+        !changed_obstacles.is_empty() || removed_obstacles.read().next().is_some();
+    if obstacles_changed {
+        // A build in progress was made from the old raster.
+        flow_field.rasterize_obstacles(obstacle_query.iter());
+        flow_field.start_build(player_cell, false);
+    } else if flow_field.build.is_none() && flow_field.player_cell != Some(player_cell) {
+        // Keep the grid centered on the player so the field covers the visible area.
+        flow_field.start_build(player_cell, true);
+    }
+
+    if flow_field.player_cell.is_none() {
+        // Nothing to fall back on yet, so finish the first field right away.
+        flow_field.step_build(usize::MAX);
+    } else {
+        flow_field.step_build(CELLS_PER_TICK);
+    }
+}
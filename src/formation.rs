@@ -0,0 +1,291 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::Rng;
+
+use crate::{
+    behavior::{EnemyBehavior, EnemyBehaviorAppExt},
+    difficulty::Difficulty,
+    enemy::{spawn_enemy, CurrentEnemyStats, EnemyStats, EnemyType, SpawnBudget},
+    player::Player,
+    rng::{GameRng, RngStream},
+    spawn_area::{SpawnArea, SpawnView},
+    GameplaySet,
+};
+
+const FORMATION_INTERVAL: f32 = 30.0;
+const WARNING_TIME: f32 = 2.0;
+// Markers sit inside the view so the player sees where the formation comes from.
+const WARNING_RADIUS: f32 = 250.0;
+const MARKER_SIZE: f32 = 24.0;
+
+const RING_COUNT: u32 = 24;
+const LINE_COUNT: u32 = 12;
+const LINE_SPACING: f32 = 50.0;
+const LINE_SPEED_SCALE: f32 = 1.5;
+const CLUSTER_COUNT: u32 = 15;
+const CLUSTER_RADIUS: f32 = 60.0;
+
+/// Large scripted spawn events that break up the steady trickle of single enemies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Formation {
+    /// A full ring around the player that closes in.
+    Ring,
+    /// A line of enemies charging straight across the screen.
+    Stampede,
+    /// A tight group bursting in from one direction.
+    Cluster,
+}
+
+impl Formation {
+    fn random(rng: &mut impl Rng) -> Self {
+        match rng.gen_range(0..3) {
+            0 => Formation::Ring,
+            1 => Formation::Stampede,
+            _ => Formation::Cluster,
+        }
+    }
+}
+
+/// Enemy running in a straight line instead of chasing the player.
+/// Despawned once it has covered `remaining` distance.
+#[derive(Component)]
+pub struct Stampede {
+    pub direction: Vec2,
+    pub remaining: f32,
+}
+
+/// Pending formation shown to the player as warning markers until it arrives.
+#[derive(Component)]
+struct FormationWarning {
+    formation: Formation,
+    // Direction (from the player) the formation arrives from.
+    direction: Vec2,
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct WarningMarker;
+
+#[derive(Resource)]
+struct FormationTimer(Timer);
+
+pub struct FormationPlugin;
+
+impl Plugin for FormationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(FormationTimer(Timer::from_seconds(
+            FORMATION_INTERVAL,
+            TimerMode::Repeating,
+        )))
+        .add_systems(
+            FixedUpdate,
+            (schedule_formations, tick_formation_warnings).in_set(GameplaySet),
+        )
+        .register_enemy_behavior(EnemyBehavior::STAMPEDE, stampede_behavior)
+        .add_systems(Update, blink_warning_markers.in_set(GameplaySet));
+    }
+}
+
+fn schedule_formations(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut formation_timer: ResMut<FormationTimer>,
+    mut game_rng: ResMut<GameRng>,
+    player_query: Query<&Transform, With<Player>>,
+) {
+    formation_timer.0.tick(time.delta());
+    if !formation_timer.0.just_finished() {
+        return;
+    }
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    let rng = game_rng.stream(RngStream::Formations);
+    let formation = Formation::random(rng);
+    let angle = rng.gen_range(0.0..360.0_f32).to_radians();
+    let direction = Vec2::new(angle.cos(), angle.sin());
+
+    spawn_formation_warning(
+        &mut commands,
+        formation,
+        direction,
+        player_transform.translation.truncate(),
+    );
+}
+
+fn spawn_formation_warning(
+    commands: &mut Commands,
+    formation: Formation,
+    direction: Vec2,
+    center: Vec2,
+) {
+    let marker_offsets: Vec<Vec2> = match formation {
+        Formation::Ring => (0..12)
+            .map(|i| {
+                let angle = i as f32 / 12.0 * std::f32::consts::TAU;
+                Vec2::new(angle.cos(), angle.sin()) * WARNING_RADIUS
+            })
+            .collect(),
+        Formation::Stampede => {
+            let across = direction.perp();
+            (-2..=2)
+                .map(|i| direction * WARNING_RADIUS + across * i as f32 * MARKER_SIZE * 2.0)
+                .collect()
+        }
+        Formation::Cluster => vec![direction * WARNING_RADIUS],
+    };
+
+    commands
+        .spawn((
+            FormationWarning {
+                formation,
+                direction,
+                timer: Timer::from_seconds(WARNING_TIME, TimerMode::Once),
+            },
+            SpatialBundle::from_transform(Transform::from_xyz(center.x, center.y, 2.0)),
+        ))
+        .with_children(|parent| {
+            for offset in marker_offsets {
+                parent.spawn((
+                    WarningMarker,
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::srgba(1.0, 0.8, 0.0, 0.8),
+                            custom_size: Some(Vec2::splat(MARKER_SIZE)),
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(offset.x, offset.y, 0.0),
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+fn tick_formation_warnings(
+    mut commands: Commands,
+    time: Res<Time>,
+    difficulty: Difficulty,
+    mut game_rng: ResMut<GameRng>,
+    spawn_view: SpawnView,
+    mut budget: ResMut<SpawnBudget>,
+    mut warning_query: Query<(Entity, &mut FormationWarning)>,
+) {
+    let Some(area) = spawn_view.area() else {
+        return;
+    };
+
+    for (entity, mut warning) in warning_query.iter_mut() {
+        warning.timer.tick(time.delta());
+        if !warning.timer.finished() {
+            continue;
+        }
+        commands.entity(entity).despawn_recursive();
+        let stats = difficulty.scale_stats(EnemyType::Grunt.get_stats());
+        spawn_formation(
+            &mut commands,
+            &mut budget,
+            game_rng.stream(RngStream::Formations),
+            area,
+            warning.formation,
+            warning.direction,
+            stats,
+        );
+    }
+}
+
+fn spawn_formation(
+    commands: &mut Commands,
+    budget: &mut SpawnBudget,
+    rng: &mut impl Rng,
+    area: SpawnArea,
+    formation: Formation,
+    direction: Vec2,
+    stats: EnemyStats,
+) {
+    let center = area.center();
+    match formation {
+        Formation::Ring => {
+            let distance = area.ring_radius(stats.size);
+            for i in 0..RING_COUNT {
+                let angle = i as f32 / RING_COUNT as f32 * std::f32::consts::TAU;
+                let position = center + Vec2::new(angle.cos(), angle.sin()) * distance;
+                spawn_enemy(commands, budget, EnemyType::Grunt, stats, position);
+            }
+        }
+        Formation::Stampede => {
+            let stats = EnemyStats {
+                speed: stats.speed * LINE_SPEED_SCALE,
+                behavior: EnemyBehavior::STAMPEDE,
+                ..stats
+            };
+            let start = area.edge_point(direction, stats.size);
+            let distance = start.distance(center);
+            let across = direction.perp();
+            let half_width = (LINE_COUNT - 1) as f32 * LINE_SPACING / 2.0;
+            for i in 0..LINE_COUNT {
+                let position = start + across * (i as f32 * LINE_SPACING - half_width);
+                let Some(enemy) = spawn_enemy(commands, budget, EnemyType::Grunt, stats, position)
+                else {
+                    break;
+                };
+                commands.entity(enemy).insert(Stampede {
+                    direction: -direction,
+                    remaining: distance * 2.0,
+                });
+            }
+        }
+        Formation::Cluster => {
+            let cluster_center = area.edge_point(direction, CLUSTER_RADIUS);
+            for _ in 0..CLUSTER_COUNT {
+                let offset = Vec2::new(
+                    rng.gen_range(-CLUSTER_RADIUS..CLUSTER_RADIUS),
+                    rng.gen_range(-CLUSTER_RADIUS..CLUSTER_RADIUS),
+                );
+                spawn_enemy(commands, budget, EnemyType::Grunt, stats, cluster_center + offset);
+            }
+        }
+    }
+}
+
+fn blink_warning_markers(
+    warning_query: Query<(&FormationWarning, &Children)>,
+    mut marker_query: Query<&mut Sprite, With<WarningMarker>>,
+) {
+    for (warning, children) in warning_query.iter() {
+        // Blink faster as the formation gets closer to arriving.
+        let elapsed = warning.timer.elapsed_secs();
+        let rate = 4.0 + 8.0 * warning.timer.fraction();
+        let alpha = if (elapsed * rate).fract() < 0.5 { 0.9 } else { 0.3 };
+        for &child in children.iter() {
+            if let Ok(mut sprite) = marker_query.get_mut(child) {
+                sprite.color.set_alpha(alpha);
+            }
+        }
+    }
+}
+
+/// Built-in `EnemyBehavior::STAMPEDE`.
+fn stampede_behavior(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut stampede_query: Query<(
+        Entity,
+        &EnemyBehavior,
+        &mut Stampede,
+        &mut Velocity,
+        &CurrentEnemyStats,
+    )>,
+) {
+    for (entity, behavior, mut stampede, mut velocity, stats) in stampede_query.iter_mut() {
+        if *behavior != EnemyBehavior::STAMPEDE {
+            continue;
+        }
+        velocity.linvel = stampede.direction * stats.speed;
+        stampede.remaining -= stats.speed * time.delta_seconds();
+        if stampede.remaining <= 0.0 {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::{
+    components::Health,
+    enemy::{CurrentEnemyStats, Enemy, EnemyType},
+    GameplaySet,
+};
+
+const BAR_HEIGHT: f32 = 5.0;
+const BAR_GAP: f32 = 6.0;
+
+#[derive(Resource, Debug, Clone, Default)]
+pub struct HealthBarConfig {
+    /// Show bars on Tanks from the moment they spawn, not only once damaged.
+    pub always_show_tanks: bool,
+}
+
+/// World-space bar floating above an enemy. Added lazily the first time the
+/// enemy needs one, so undamaged swarms don't pay for extra entities.
+#[derive(Component)]
+pub struct HealthBar {
+    root: Entity,
+    fill: Entity,
+    width: f32,
+}
+
+pub struct HealthBarPlugin;
+
+impl Plugin for HealthBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HealthBarConfig>().add_systems(
+            Update,
+            (attach_health_bars, update_health_bars)
+                .chain()
+                .in_set(GameplaySet),
+        );
+    }
+}
+
+fn wants_health_bar(config: &HealthBarConfig, health: &Health, enemy_type: EnemyType) -> bool {
+    !health.is_full() || (config.always_show_tanks && enemy_type == EnemyType::Tank)
+}
+
+fn attach_health_bars(
+    mut commands: Commands,
+    config: Res<HealthBarConfig>,
+    query: Query<(Entity, &Health, &EnemyType, &CurrentEnemyStats), (With<Enemy>, Without<HealthBar>)>,
+) {
+    for (entity, health, enemy_type, stats) in query.iter() {
+        if !wants_health_bar(&config, health, *enemy_type) {
+            continue;
+        }
+
+        let width = stats.size;
+        let offset = stats.size / 2.0 + BAR_GAP;
+        let mut fill = Entity::PLACEHOLDER;
+        let root = commands
+            .spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::srgb(0.15, 0.15, 0.15),
+                    custom_size: Some(Vec2::new(width, BAR_HEIGHT)),
+                    ..default()
+                },
+                transform: Transform::from_xyz(0.0, offset, 5.0),
+                ..default()
+            })
+            .with_children(|parent| {
+                // Anchored on the left edge so shrinking the width drains it right to left.
+                fill = parent
+                    .spawn(SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::srgb(0.9, 0.1, 0.1),
+                            custom_size: Some(Vec2::new(width * health.fraction(), BAR_HEIGHT)),
+                            anchor: Anchor::CenterLeft,
+                            ..default()
+                        },
+                        transform: Transform::from_xyz(-width / 2.0, 0.0, 0.1),
+                        ..default()
+                    })
+                    .id();
+            })
+            .id();
+
+        commands
+            .entity(entity)
+            .add_child(root)
+            .insert(HealthBar { root, fill, width });
+    }
+}
+
+fn update_health_bars(
+    config: Res<HealthBarConfig>,
+    query: Query<(&Health, &EnemyType, &HealthBar), Changed<Health>>,
+    mut sprites: Query<&mut Sprite>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    for (health, enemy_type, bar) in query.iter() {
+        if let Ok(mut sprite) = sprites.get_mut(bar.fill) {
+            sprite.custom_size = Some(Vec2::new(bar.width * health.fraction(), BAR_HEIGHT));
+        }
+        if let Ok(mut visibility) = visibilities.get_mut(bar.root) {
+            *visibility = if wants_health_bar(&config, health, *enemy_type) {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! The game as a library, so other crates can extend it without forking, e.g.
+//! by registering enemy behaviors through `behavior::EnemyBehaviorAppExt` or
+//! weapon kinds through `weapon::WeaponAppExt`.
+//! The `bev` binary just runs `GamePlugin`.
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use leafwing_input_manager::prelude::InputManagerPlugin;
+
+pub mod animation;
+pub mod aura;
+pub mod behavior;
+pub mod bolt;
+pub mod boomerang;
+pub mod chain_lightning;
+pub mod combat;
+pub mod components;
+pub mod difficulty;
+pub mod enemy;
+pub mod experience;
+pub mod flow_field;
+pub mod formation;
+pub mod health_bar;
+pub mod loot;
+pub mod mine;
+pub mod movement;
+pub mod pause;
+pub mod player;
+pub mod projectile;
+pub mod rng;
+pub mod simulation;
+pub mod spatial;
+pub mod spawn_area;
+pub mod summoner;
+pub mod targeting;
+pub mod ui;
+pub mod weapon;
+pub mod whip;
+
+use animation::AnimationPlugin;
+use aura::AuraPlugin;
+use behavior::BehaviorPlugin;
+use bolt::BoltPlugin;
+use boomerang::BoomerangPlugin;
+use chain_lightning::ChainLightningPlugin;
+use combat::CombatPlugin;
+use difficulty::DifficultyPlugin;
+use enemy::EnemyPlugin;
+use experience::ExperiencePlugin;
+use flow_field::FlowFieldPlugin;
+use formation::FormationPlugin;
+use health_bar::HealthBarPlugin;
+use loot::LootPlugin;
+use mine::MinePlugin;
+use movement::MovementPlugin;
+use pause::PausePlugin;
+use player::PlayerPlugin;
+use projectile::ProjectilePlugin;
+use rng::RngPlugin;
+use simulation::SimulationPlugin;
+use spatial::SpatialPlugin;
+use summoner::SummonerPlugin;
+use ui::UiPlugin;
+use weapon::WeaponPlugin;
+use whip::WhipPlugin;
+
+// Central game states for the project.
+#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub enum GameState {
+    #[default]
+    Gameplay,
+    LevelUp,
+    Paused,
+    GameOver,
+}
+
+/// Systems that advance the game world. They only run in `GameState::Gameplay`,
+/// so the world stays frozen while paused or on the level-up screen.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
+/// The whole game on top of `DefaultPlugins`. Plugins from other crates can be
+/// added next to it and use the registration APIs of the exported modules.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_state::<GameState>()
+            .configure_sets(Update, GameplaySet.run_if(in_state(GameState::Gameplay)))
+            // Gameplay drives bodies before rapier syncs and steps them within the same tick.
+            .configure_sets(
+                FixedUpdate,
+                GameplaySet
+                    .run_if(in_state(GameState::Gameplay))
+                    .before(PhysicsSet::SyncBackend),
+            )
+            // Global input configuration resource used for constructing input maps and runtime remapping
+            .insert_resource(movement::InputConfig::default())
+            // Physics, stepped alongside gameplay in the fixed schedule
+            .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0).in_fixed_schedule())
+            .add_plugins(RapierDebugRenderPlugin::default())
+            // Input manager plugin for remappable actions
+            .add_plugins(InputManagerPlugin::<movement::Action>::default())
+            // Core run infrastructure
+            .add_plugins((RngPlugin, PausePlugin, SimulationPlugin, BehaviorPlugin))
+            // Enemy plugins
+            .add_plugins((
+                DifficultyPlugin,
+                FlowFieldPlugin,
+                EnemyPlugin,
+                FormationPlugin,
+                SummonerPlugin,
+            ))
+            // Weapon plugins
+            .add_plugins((
+                WeaponPlugin,
+                ProjectilePlugin,
+                BoltPlugin,
+                ChainLightningPlugin,
+                AuraPlugin,
+                WhipPlugin,
+                BoomerangPlugin,
+                MinePlugin,
+            ))
+            // Game feature plugins
+            .add_plugins((
+                PlayerPlugin,
+                MovementPlugin,
+                CombatPlugin,
+                HealthBarPlugin,
+                AnimationPlugin,
+                ExperiencePlugin,
+                LootPlugin,
+                SpatialPlugin,
+                UiPlugin,
+            ));
+    }
+}
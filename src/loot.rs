@@ -1,10 +1,11 @@
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
-use crate::{components::Health, player::Player, GameState};
+use crate::{components::Health, player::Player, GameplaySet};
 
 const HEALTH_PACK_SIZE: f32 = 20.0;
 const HEALTH_PACK_VALUE: f32 = 25.0;
+const GOLD_COIN_SIZE: f32 = 12.0;
 
 #[derive(Component)]
 pub struct LootDrop {
@@ -13,36 +14,45 @@ pub struct LootDrop {
 
 pub enum LootType {
     HealthPack,
+    Gold { amount: u32 },
+}
+
+/// Gold collected during the current run.
+#[derive(Resource, Debug, Default)]
+pub struct Wallet {
+    pub gold: u32,
 }
 
 pub struct LootPlugin;
 
 impl Plugin for LootPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            handle_loot_collection.run_if(in_state(GameState::Gameplay)),
+        app.init_resource::<Wallet>().add_systems(
+            FixedUpdate,
+            handle_loot_collection.in_set(GameplaySet),
         );
     }
 }
 
-pub fn spawn_loot_drop(commands: &mut Commands, position: Vec2) {
-    // For now, only health packs can drop
-    let loot_type = LootType::HealthPack;
+pub fn spawn_loot_drop(commands: &mut Commands, position: Vec2, loot_type: LootType) {
+    let (color, size) = match loot_type {
+        LootType::HealthPack => (Color::srgb(0.2, 0.8, 0.2), HEALTH_PACK_SIZE), // Green
+        LootType::Gold { .. } => (Color::srgb(1.0, 0.84, 0.0), GOLD_COIN_SIZE), // Gold
+    };
 
     commands
         .spawn(LootDrop { loot_type })
         .insert(SpriteBundle {
             sprite: Sprite {
-                color: Color::srgb(0.2, 0.8, 0.2), // Green
-                custom_size: Some(Vec2::new(HEALTH_PACK_SIZE, HEALTH_PACK_SIZE)),
+                color,
+                custom_size: Some(Vec2::new(size, size)),
                 ..default()
             },
             transform: Transform::from_xyz(position.x, position.y, 0.0),
             ..default()
         })
         .insert(RigidBody::Dynamic)
-        .insert(Collider::ball(HEALTH_PACK_SIZE / 2.0))
+        .insert(Collider::ball(size / 2.0))
         .insert(Sensor);
 }
 
@@ -50,6 +60,7 @@ fn handle_loot_collection(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
     mut player_query: Query<(Entity, &mut Health), With<Player>>,
+    mut wallet: ResMut<Wallet>,
     loot_query: Query<(Entity, &LootDrop)>,
 ) {
     let (player_entity, mut player_health) = match player_query.get_single_mut() {
@@ -76,11 +87,13 @@ fn handle_loot_collection(
             if other_entity == player_entity {
                 match loot_drop.loot_type {
                     LootType::HealthPack => {
-                        player_health.value += HEALTH_PACK_VALUE;
-                        // Optional: Clamp health to a max value
-                        // player_health.value = player_health.value.min(MAX_PLAYER_HEALTH);
+                        player_health.value =
+                            (player_health.value + HEALTH_PACK_VALUE).min(player_health.max);
                         println!("Collected a health pack! Current health: {}", player_health.value);
                     }
+                    LootType::Gold { amount } => {
+                        wallet.gold += amount;
+                    }
                 }
                 commands.entity(loot_entity).despawn();
             }
@@ -1,55 +1,10 @@
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::*;
-use leafwing_input_manager::prelude::InputManagerPlugin;
 
-mod combat;
-mod components;
-mod enemy;
-mod experience;
-mod loot;
-mod movement;
-mod player;
-mod ui;
-mod weapon;
-
-use combat::CombatPlugin;
-use enemy::EnemyPlugin;
-use experience::ExperiencePlugin;
-use loot::LootPlugin;
-use movement::MovementPlugin;
-use player::PlayerPlugin;
-use ui::UiPlugin;
-use weapon::WeaponPlugin;
-
-// Central game states for the project.
-#[derive(States, Debug, Clone, PartialEq, Eq, Hash, Default)]
-pub enum GameState {
-    #[default]
-    Gameplay,
-    LevelUp,
-}
+use bev::GamePlugin;
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_state::<GameState>()
-        // Global input configuration resource used for constructing input maps and runtime remapping
-        .insert_resource(movement::InputConfig::default())
-        // Physics
-        .add_plugins(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(100.0))
-        .add_plugins(RapierDebugRenderPlugin::default())
-        // Input manager plugin for remappable actions
-        .add_plugins(InputManagerPlugin::<movement::Action>::default())
-        // Game feature plugins
-        .add_plugins((
-            PlayerPlugin,
-            MovementPlugin,
-            EnemyPlugin,
-            WeaponPlugin,
-            CombatPlugin,
-            ExperiencePlugin,
-            LootPlugin,
-            UiPlugin,
-        ))
+        .add_plugins(GamePlugin)
         .run();
 }
@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    combat::{DamageEvent, DamageKind},
+    enemy::Enemy,
+    player::Player,
+    projectile::Lifetime,
+    spatial::{SpatialIndex, SpatialKind},
+    weapon::{
+        spawn_weapon, StatChange, WeaponAppExt, WeaponCooldown, WeaponDefinition, WeaponId,
+        WeaponStats,
+    },
+};
+
+const MINE_SIZE: f32 = 14.0;
+const TRIGGER_RADIUS: f32 = 40.0;
+const BLAST_RADIUS: f32 = 110.0;
+const ARM_DELAY: f32 = 0.75;
+const MAX_ACTIVE_MINES: usize = 8;
+// Mines dropped together are spread out so they don't stack.
+const DROP_SPREAD: f32 = 30.0;
+const BLAST_DURATION: f32 = 0.2;
+const UNARMED_COLOR: Color = Color::srgb(0.4, 0.4, 0.3);
+const ARMED_COLOR: Color = Color::srgb(1.0, 0.3, 0.1);
+
+/// Marks the mine weapon's `WeaponCooldown`.
+#[derive(Component, Default)]
+pub struct MineLayer;
+
+/// A mine on the ground. Explodes once armed and an enemy is inside its trigger sensor.
+#[derive(Component)]
+pub struct Mine {
+    owner: Entity,
+    arm_timer: Timer,
+    damage: f32,
+    blast_radius: f32,
+}
+
+pub struct MinePlugin;
+
+impl Plugin for MinePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_weapon(
+            WeaponDefinition {
+                id: WeaponId::MINE,
+                name: "Mines",
+                amount_label: "mine",
+                base_stats: WeaponStats {
+                    damage: 30.0,
+                    cooldown: 2.5,
+                    ..default()
+                },
+                levels: &[
+                    &[StatChange::Damage(10.0)],
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Area(0.25)],
+                    &[StatChange::Damage(10.0)],
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Cooldown(-0.15)],
+                    &[StatChange::Area(0.25)],
+                ],
+                spawn: spawn_weapon::<MineLayer>,
+            },
+            (drop_mines, arm_mines, detonate_mines).chain(),
+        );
+    }
+}
+
+fn drop_mines(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<&Transform, With<Player>>,
+    mine_query: Query<&Mine>,
+    mut layer_query: Query<(&Parent, &mut WeaponCooldown), With<MineLayer>>,
+) {
+    for (parent, mut cooldown) in layer_query.iter_mut() {
+        let owner = parent.get();
+        let Ok(player_transform) = player_query.get(owner) else {
+            continue;
+        };
+        if !cooldown.tick(time.delta()) {
+            continue;
+        }
+
+        let stats = cooldown.stats();
+        let active = mine_query.iter().filter(|mine| mine.owner == owner).count();
+        let count = MAX_ACTIVE_MINES.saturating_sub(active).min(stats.amount as usize);
+        let origin = player_transform.translation.truncate();
+        for i in 0..count {
+            let offset = if count > 1 {
+                Vec2::from_angle(i as f32 / count as f32 * std::f32::consts::TAU) * DROP_SPREAD
+            } else {
+                Vec2::ZERO
+            };
+            spawn_mine(&mut commands, owner, origin + offset, &stats);
+        }
+    }
+}
+
+fn spawn_mine(commands: &mut Commands, owner: Entity, position: Vec2, stats: &WeaponStats) {
+    commands
+        .spawn(Mine {
+            owner,
+            arm_timer: Timer::from_seconds(ARM_DELAY, TimerMode::Once),
+            damage: stats.damage,
+            blast_radius: BLAST_RADIUS * stats.area,
+        })
+        .insert(SpriteBundle {
+            sprite: Sprite {
+                color: UNARMED_COLOR,
+                custom_size: Some(Vec2::new(MINE_SIZE, MINE_SIZE)),
+                ..default()
+            },
+            transform: Transform::from_xyz(position.x, position.y, -0.5),
+            ..default()
+        })
+        // Trigger area, separate from the visible mine.
+        .insert(Collider::ball(TRIGGER_RADIUS))
+        .insert(Sensor);
+}
+
+fn arm_mines(time: Res<Time>, mut query: Query<(&mut Mine, &mut Sprite)>) {
+    for (mut mine, mut sprite) in query.iter_mut() {
+        if mine.arm_timer.finished() {
+            continue;
+        }
+        if mine.arm_timer.tick(time.delta()).just_finished() {
+            sprite.color = ARMED_COLOR;
+        }
+    }
+}
+
+/// Armed mines explode when any enemy is inside their trigger sensor. Uses the
+/// current intersections rather than `CollisionEvent::Started`, so enemies that
+/// were already standing on the mine when it armed still set it off.
+fn detonate_mines(
+    mut commands: Commands,
+    rapier_context: Res<RapierContext>,
+    index: Res<SpatialIndex>,
+    mut damage_events: EventWriter<DamageEvent>,
+    enemy_query: Query<(), With<Enemy>>,
+    mine_query: Query<(Entity, &Mine, &Transform)>,
+) {
+    for (entity, mine, transform) in mine_query.iter() {
+        if !mine.arm_timer.finished() {
+            continue;
+        }
+        let triggered = rapier_context
+            .intersection_pairs_with(entity)
+            .any(|(a, b, intersecting)| {
+                let other = if a == entity { b } else { a };
+                intersecting && enemy_query.contains(other)
+            });
+        if !triggered {
+            continue;
+        }
+
+        let center = transform.translation.truncate();
+        index.for_each_within(center, mine.blast_radius, SpatialKind::Enemy, |entry| {
+            damage_events.send(DamageEvent {
+                target: entry.entity,
+                amount: mine.damage,
+                kind: DamageKind::Explosion,
+            });
+        });
+        commands.entity(entity).despawn_recursive();
+        spawn_blast(&mut commands, center, mine.blast_radius);
+    }
+}
+
+fn spawn_blast(commands: &mut Commands, center: Vec2, radius: f32) {
+    commands
+        .spawn(SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(1.0, 0.5, 0.1, 0.5),
+                custom_size: Some(Vec2::splat(radius * 2.0)),
+                ..default()
+            },
+            transform: Transform::from_xyz(center.x, center.y, 2.0),
+            ..default()
+        })
+        .insert(Lifetime(Timer::from_seconds(BLAST_DURATION, TimerMode::Once)));
+}
@@ -0,0 +1,53 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use leafwing_input_manager::prelude::ActionState;
+
+use crate::{movement::Action, player::Player, GameState};
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        // Virtual time and physics only advance during gameplay; every other
+        // state (pause menu, level-up screen) freezes the world.
+        app.add_systems(OnEnter(GameState::Gameplay), resume_world)
+            .add_systems(OnExit(GameState::Gameplay), freeze_world)
+            .add_systems(Update, toggle_pause);
+    }
+}
+
+fn toggle_pause(
+    action_states: Query<&ActionState<Action>, With<Player>>,
+    state: Res<State<GameState>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Ok(action_state) = action_states.get_single() else {
+        return;
+    };
+    if !action_state.just_pressed(Action::Pause) {
+        return;
+    }
+
+    match state.get() {
+        GameState::Gameplay => next_state.set(GameState::Paused),
+        GameState::Paused => next_state.set(GameState::Gameplay),
+        // Menus like the level-up screen have to be resolved first.
+        _ => {}
+    }
+}
+
+fn freeze_world(
+    mut time: ResMut<Time<Virtual>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    time.pause();
+    rapier_config.physics_pipeline_active = false;
+}
+
+fn resume_world(
+    mut time: ResMut<Time<Virtual>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    time.unpause();
+    rapier_config.physics_pipeline_active = true;
+}
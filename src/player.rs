@@ -4,12 +4,14 @@ use leafwing_input_manager::prelude::{Actionlike, InputMap};
 use leafwing_input_manager::prelude::controller::GamepadButtonType;
 
 use crate::{
+    animation::{SpriteAnimation, SpriteSheet},
     components::Health,
-    movement::{Action, Velocity, DebugUi, InputConfig},
-    weapon::spawn_orbital_weapon,
+    movement::{Action, Velocity, DebugUi, Facing, InputConfig},
+    weapon::{Loadout, WeaponId, WeaponRegistry},
 };
 
 const PLAYER_SIZE: f32 = 32.0;
+const PLAYER_SHEET: SpriteSheet = SpriteSheet::standard("sprites/player.png", 32, 6, 200.0);
 
 /// Marker component for the player entity.
 #[derive(Component)]
@@ -26,7 +28,7 @@ impl Plugin for PlayerPlugin {
 
 /// Spawn a simple player as a colored square sprite and give it a `Velocity` component.
 /// Also attach an `InputManagerBundle` so the player entity has remappable input actions.
-fn setup_player(mut commands: Commands) {
+fn setup_player(mut commands: Commands, weapon_registry: Res<WeaponRegistry>) {
     // Spawn a simple 2D camera so the scene is visible.
     commands.spawn(Camera2dBundle::default());
 
@@ -48,8 +50,11 @@ fn setup_player(mut commands: Commands) {
     input_map.insert(GamepadButtonType::South, Action::Ability1);
     input_map.insert(GamepadButtonType::East, Action::Ability2);
     input_map.insert(GamepadButtonType::North, Action::Ability3);
+    input_map.insert(KeyCode::Escape, Action::Pause);
+    input_map.insert(GamepadButtonType::Start, Action::Pause);
 
-    let weapon_entity = spawn_orbital_weapon(&mut commands);
+    let mut loadout = Loadout::default();
+    loadout.add(WeaponId::ORBITAL, &weapon_registry);
 
     // Spawn the player as a colored square sprite with an initial zero velocity.
     commands
@@ -64,9 +69,12 @@ fn setup_player(mut commands: Commands) {
                 ..Default::default()
             },
             Player,
-            Health { value: 100.0 },
+            SpriteAnimation::new(PLAYER_SHEET),
+            Health::new(100.0),
+            loadout,
             // Start stationary
             Velocity(Vec2::ZERO),
+            Facing::default(),
             // Attach input manager bundle with our action map so the player entity can receive action state
             leafwing_input_manager::InputManagerBundle {
                 input_map,
@@ -75,8 +83,7 @@ fn setup_player(mut commands: Commands) {
             RigidBody::Dynamic,
             Collider::capsule_y(PLAYER_SIZE / 4.0, PLAYER_SIZE / 4.0),
             ActiveEvents::COLLISION_EVENTS,
-        ))
-        .add_child(weapon_entity);
+        ));
 
     // Spawn a simple debug UI text in the top-left that will be updated each frame.
     let font = AssetServer::load("fonts/FiraSans-Bold.ttf");
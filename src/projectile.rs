@@ -0,0 +1,241 @@
+use bevy::ecs::system::EntityCommands;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity as BodyVelocity;
+
+use crate::{
+    combat::{handle_collisions, DamageEvent, DamageKind},
+    spatial::{SpatialIndex, SpatialKind},
+    weapon::{Damage, Projectile},
+    GameplaySet,
+};
+
+// Damage for projectiles spawned without a `Damage` component.
+const PROJECTILE_DAMAGE: f32 = 10.0;
+
+// How far homing projectiles look for a target.
+const HOMING_RANGE: f32 = 300.0;
+const BOUNCE_RANGE: f32 = 250.0;
+// Radians per second added by each homing upgrade.
+const HOMING_UPGRADE_TURN_RATE: f32 = 2.0;
+
+/// Despawns the entity once the timer runs out. Also used for short-lived effects.
+#[derive(Component)]
+pub struct Lifetime(pub Timer);
+
+/// Despawns the projectile once it is `range` away from where it was fired.
+#[derive(Component)]
+pub struct MaxRange {
+    pub origin: Vec2,
+    pub range: f32,
+}
+
+/// Number of enemies the projectile can hit before it is used up.
+#[derive(Component)]
+pub struct Pierce {
+    pub remaining: u32,
+}
+
+/// On hit, redirect towards another nearby enemy instead of using up pierce.
+#[derive(Component)]
+pub struct Bounce {
+    pub remaining: u32,
+}
+
+/// Steer towards the nearest enemy by up to `turn_rate` radians per second.
+#[derive(Component)]
+pub struct Homing {
+    pub turn_rate: f32,
+}
+
+/// A projectile touched an enemy. Sent by `handle_collisions`; whether it
+/// deals damage is decided by `resolve_projectile_hits`.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct ProjectileHit {
+    pub projectile: Entity,
+    pub enemy: Entity,
+}
+
+/// Base behaviour a weapon gives its projectiles, before global modifiers.
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectileProperties {
+    pub lifetime: f32,
+    pub range: f32,
+    pub pierce: u32,
+    pub bounces: u32,
+    pub homing: f32,
+}
+
+impl ProjectileProperties {
+    /// Insert the components for these properties on a projectile fired from `origin`.
+    pub fn insert(&self, projectile: &mut EntityCommands, origin: Vec2) {
+        projectile
+            .insert(Lifetime(Timer::from_seconds(self.lifetime, TimerMode::Once)))
+            .insert(MaxRange {
+                origin,
+                range: self.range,
+            })
+            .insert(Pierce {
+                remaining: self.pierce,
+            });
+        if self.bounces > 0 {
+            projectile.insert(Bounce {
+                remaining: self.bounces,
+            });
+        }
+        if self.homing > 0.0 {
+            projectile.insert(Homing {
+                turn_rate: self.homing,
+            });
+        }
+    }
+}
+
+/// Upgrades that apply to every projectile weapon.
+#[derive(Resource, Debug, Clone)]
+pub struct ProjectileModifiers {
+    pub extra_pierce: u32,
+    pub extra_bounces: u32,
+    pub homing_upgrades: u32,
+    /// Multipliers on how long and how far projectiles fly.
+    pub lifetime_multiplier: f32,
+    pub range_multiplier: f32,
+}
+
+impl Default for ProjectileModifiers {
+    fn default() -> Self {
+        Self {
+            extra_pierce: 0,
+            extra_bounces: 0,
+            homing_upgrades: 0,
+            lifetime_multiplier: 1.0,
+            range_multiplier: 1.0,
+        }
+    }
+}
+
+impl ProjectileModifiers {
+    pub fn apply(&self, base: ProjectileProperties) -> ProjectileProperties {
+        ProjectileProperties {
+            lifetime: base.lifetime * self.lifetime_multiplier,
+            range: base.range * self.range_multiplier,
+            pierce: base.pierce + self.extra_pierce,
+            bounces: base.bounces + self.extra_bounces,
+            homing: base.homing + self.homing_upgrades as f32 * HOMING_UPGRADE_TURN_RATE,
+            ..base
+        }
+    }
+}
+
+pub struct ProjectilePlugin;
+
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ProjectileModifiers>()
+            .add_event::<ProjectileHit>()
+            .add_systems(
+                FixedUpdate,
+                (
+                    expire_projectiles,
+                    steer_homing_projectiles,
+                    resolve_projectile_hits.after(handle_collisions),
+                )
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn expire_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut lifetime_query: Query<(Entity, &mut Lifetime)>,
+    range_query: Query<(Entity, &Transform, &MaxRange)>,
+) {
+    for (entity, mut lifetime) in lifetime_query.iter_mut() {
+        if lifetime.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    for (entity, transform, max_range) in range_query.iter() {
+        if transform.translation.truncate().distance(max_range.origin) > max_range.range {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn steer_homing_projectiles(
+    time: Res<Time>,
+    index: Res<SpatialIndex>,
+    mut query: Query<(&Transform, &Homing, &mut BodyVelocity)>,
+) {
+    let delta = time.delta_seconds();
+    for (transform, homing, mut velocity) in query.iter_mut() {
+        let position = transform.translation.truncate();
+        let Some(target) = index.nearest(position, HOMING_RANGE, SpatialKind::Enemy) else {
+            continue;
+        };
+        let desired = target.position - position;
+        let angle = velocity.linvel.angle_between(desired);
+        if !angle.is_finite() {
+            continue;
+        }
+        let max_turn = homing.turn_rate * delta;
+        let turn = angle.clamp(-max_turn, max_turn);
+        velocity.linvel = Vec2::from_angle(turn).rotate(velocity.linvel);
+    }
+}
+
+/// Deal damage for each hit while the projectile has pierce left, then bounce
+/// it to a new target or use up pierce and despawn spent ones.
+pub(crate) fn resolve_projectile_hits(
+    mut commands: Commands,
+    mut hits: EventReader<ProjectileHit>,
+    mut damage_events: EventWriter<DamageEvent>,
+    index: Res<SpatialIndex>,
+    damage_query: Query<Option<&Damage>, With<Projectile>>,
+    mut query: Query<(
+        &Transform,
+        &mut Pierce,
+        Option<&mut Bounce>,
+        &mut BodyVelocity,
+    )>,
+) {
+    for hit in hits.read() {
+        let damage = DamageEvent {
+            target: hit.enemy,
+            amount: damage_query
+                .get(hit.projectile)
+                .ok()
+                .flatten()
+                .map_or(PROJECTILE_DAMAGE, |damage| damage.0),
+            kind: DamageKind::Physical,
+        };
+        let Ok((transform, mut pierce, bounce, mut velocity)) = query.get_mut(hit.projectile) else {
+            // Without pierce (orbitals, whip lashes) every touch is a hit.
+            damage_events.send(damage);
+            continue;
+        };
+        if pierce.remaining == 0 {
+            // Already spent by an earlier hit this tick.
+            continue;
+        }
+        damage_events.send(damage);
+
+        if let Some(mut bounce) = bounce.filter(|bounce| bounce.remaining > 0) {
+            let position = transform.translation.truncate();
+            let next = index
+                .nearest_k(position, 2, BOUNCE_RANGE, SpatialKind::Enemy)
+                .into_iter()
+                .find(|entry| entry.entity != hit.enemy);
+            if let Some(direction) = next.and_then(|next| (next.position - position).try_normalize()) {
+                bounce.remaining -= 1;
+                velocity.linvel = direction * velocity.linvel.length();
+                continue;
+            }
+        }
+
+        pierce.remaining -= 1;
+        if pierce.remaining == 0 {
+            commands.entity(hit.projectile).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use bevy::math::{Affine3A, Vec3A};
+use bevy::prelude::*;
+use bevy::transform::TransformSystem;
+use bevy_rapier2d::prelude::*;
+
+const DEFAULT_TICK_RATE: f64 = 60.0;
+
+/// Fixed-timestep settings for the gameplay simulation.
+///
+/// Gameplay systems run in `FixedUpdate`, so the outcome of a run does not
+/// depend on the display frame rate; rendering interpolates in between ticks.
+#[derive(Resource, Debug, Clone)]
+pub struct SimulationConfig {
+    /// Simulation ticks per second.
+    pub tick_rate: f64,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            tick_rate: DEFAULT_TICK_RATE,
+        }
+    }
+}
+
+/// Translation of a simulated body at the last two fixed ticks. Rendering
+/// blends between them so motion stays smooth at any frame rate.
+///
+/// The blend is only written to `GlobalTransform` after transform propagation,
+/// and undone before the next tick, so the body's `Transform` always holds the
+/// simulated position and rapier never sees the rendered one.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct InterpolatedPosition {
+    pub previous: Vec3,
+    pub current: Vec3,
+}
+
+impl InterpolatedPosition {
+    pub fn new(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+        }
+    }
+
+    /// Jump to `translation` without blending from the old position.
+    pub fn teleport(&mut self, translation: Vec3) {
+        self.previous = translation;
+        self.current = translation;
+    }
+}
+
+/// Simulated `GlobalTransform` translation of every entity currently drawn
+/// somewhere else, so it can be put back exactly.
+#[derive(Resource, Default)]
+struct RenderDisplacements(HashMap<Entity, Vec3A>);
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        let config = SimulationConfig::default();
+        if let Some(mut rapier_config) = app.world_mut().get_resource_mut::<RapierConfiguration>() {
+            rapier_config.timestep_mode = physics_timestep(config.tick_rate);
+        }
+        app.insert_resource(Time::<Fixed>::from_hz(config.tick_rate))
+            .insert_resource(config)
+            .init_resource::<RenderDisplacements>()
+            .add_systems(Update, apply_tick_rate)
+            .add_systems(FixedFirst, restore_simulated_transforms)
+            .add_systems(
+                FixedLast,
+                (track_new_bodies, record_simulated_positions).chain(),
+            )
+            .add_systems(
+                PostUpdate,
+                interpolate_rendered_positions.after(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Rapier steps once per fixed tick, by exactly one tick.
+fn physics_timestep(tick_rate: f64) -> TimestepMode {
+    TimestepMode::Fixed {
+        dt: (1.0 / tick_rate) as f32,
+        substeps: 1,
+    }
+}
+
+fn apply_tick_rate(
+    config: Res<SimulationConfig>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut rapier_config: ResMut<RapierConfiguration>,
+) {
+    if config.is_changed() {
+        fixed_time.set_timestep_hz(config.tick_rate);
+        rapier_config.timestep_mode = physics_timestep(config.tick_rate);
+    }
+}
+
+/// Every moving physics body gets interpolated rendering.
+fn track_new_bodies(
+    mut commands: Commands,
+    query: Query<(Entity, &Transform, &RigidBody), (Added<RigidBody>, Without<InterpolatedPosition>)>,
+) {
+    for (entity, transform, body) in query.iter() {
+        if *body != RigidBody::Fixed {
+            commands
+                .entity(entity)
+                .insert(InterpolatedPosition::new(transform.translation));
+        }
+    }
+}
+
+fn record_simulated_positions(mut query: Query<(&Transform, &mut InterpolatedPosition)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.previous = interpolated.current;
+        interpolated.current = transform.translation;
+    }
+}
+
+fn with_translation(global: &GlobalTransform, translation: Vec3A) -> GlobalTransform {
+    let mut affine = global.affine();
+    affine.translation = translation;
+    GlobalTransform::from(affine)
+}
+
+/// Undo the render-time blend so the tick, and rapier's change detection, see
+/// the same `GlobalTransform` propagation produced.
+fn restore_simulated_transforms(
+    mut displaced: ResMut<RenderDisplacements>,
+    mut query: Query<&mut GlobalTransform>,
+) {
+    for (entity, translation) in displaced.0.drain() {
+        if let Ok(mut global) = query.get_mut(entity) {
+            *global = with_translation(&global, translation);
+        }
+    }
+}
+
+/// Draw interpolated bodies, and everything attached to them, at the blended
+/// position by offsetting their already propagated `GlobalTransform`.
+fn interpolate_rendered_positions(
+    fixed_time: Res<Time<Fixed>>,
+    mut displaced: ResMut<RenderDisplacements>,
+    root_query: Query<Entity, (With<InterpolatedPosition>, Without<Parent>)>,
+    mut node_query: Query<(&mut GlobalTransform, Option<&InterpolatedPosition>, Option<&Children>)>,
+) {
+    // Without a tick this frame, propagation skipped unchanged entities and
+    // they still carry last frame's offset.
+    for (entity, translation) in displaced.0.drain() {
+        if let Ok((mut global, ..)) = node_query.get_mut(entity) {
+            if !global.is_changed() {
+                *global = with_translation(&global, translation);
+            }
+        }
+    }
+
+    let alpha = fixed_time.overstep_fraction();
+    for root in root_query.iter() {
+        displace_rendered(root, None, Vec3::ZERO, alpha, &mut node_query, &mut displaced.0);
+    }
+}
+
+fn displace_rendered(
+    entity: Entity,
+    parent: Option<Affine3A>,
+    inherited: Vec3,
+    alpha: f32,
+    node_query: &mut Query<(&mut GlobalTransform, Option<&InterpolatedPosition>, Option<&Children>)>,
+    displaced: &mut HashMap<Entity, Vec3A>,
+) {
+    let Ok((mut global, interpolated, children)) = node_query.get_mut(entity) else {
+        return;
+    };
+    let mut offset = inherited;
+    if let Some(interpolated) = interpolated {
+        // The lag is in the parent's space.
+        let lag = interpolated.previous.lerp(interpolated.current, alpha) - interpolated.current;
+        offset += parent.map_or(lag, |parent| parent.transform_vector3(lag));
+    }
+    let simulated = global.affine();
+    let children = children.map(|children| children.to_vec()).unwrap_or_default();
+    if offset != Vec3::ZERO {
+        displaced.insert(entity, simulated.translation);
+        *global = with_translation(&global, simulated.translation + Vec3A::from(offset));
+    }
+
+    for child in children {
+        displace_rendered(child, Some(simulated), offset, alpha, node_query, displaced);
+    }
+}
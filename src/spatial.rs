@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::{enemy::Enemy, experience::ExperienceGem, loot::LootDrop};
+
+const SPATIAL_CELL_SIZE: f32 = 64.0;
+
+/// What kind of entity an index entry refers to, so queries can filter cheaply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialKind {
+    Enemy,
+    Pickup,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub kind: SpatialKind,
+}
+
+/// Uniform hash grid over enemy and pickup positions, rebuilt every fixed tick.
+///
+/// Use it for "everything within radius R of P" questions (targeting, auras,
+/// magnets, explosions) instead of going through the physics engine.
+#[derive(Resource)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    entries: Vec<SpatialEntry>,
+    cells: HashMap<IVec2, Vec<usize>>,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(SPATIAL_CELL_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            entries: Vec::new(),
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell_of(&self, position: Vec2) -> IVec2 {
+        (position / self.cell_size).floor().as_ivec2()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        // Keep the allocations of cells used last tick, since most are reused next
+        // tick, but drop cells that stayed empty so the map doesn't keep every cell
+        // the player has ever passed through.
+        self.cells.retain(|_, bucket| {
+            let used = !bucket.is_empty();
+            bucket.clear();
+            used
+        });
+    }
+
+    pub fn insert(&mut self, entity: Entity, position: Vec2, kind: SpatialKind) {
+        let cell = self.cell_of(position);
+        self.cells.entry(cell).or_default().push(self.entries.len());
+        self.entries.push(SpatialEntry {
+            entity,
+            position,
+            kind,
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Call `f` for every entry of `kind` within `radius` of `point`.
+    pub fn for_each_within(
+        &self,
+        point: Vec2,
+        radius: f32,
+        kind: SpatialKind,
+        mut f: impl FnMut(&SpatialEntry),
+    ) {
+        let min = self.cell_of(point - Vec2::splat(radius));
+        let max = self.cell_of(point + Vec2::splat(radius));
+        let radius_sq = radius * radius;
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let Some(bucket) = self.cells.get(&IVec2::new(x, y)) else {
+                    continue;
+                };
+                for &i in bucket {
+                    let entry = &self.entries[i];
+                    if entry.kind == kind && entry.position.distance_squared(point) <= radius_sq {
+                        f(entry);
+                    }
+                }
+            }
+        }
+    }
+
+    /// All entries of `kind` within `radius` of `point`, in no particular order.
+    pub fn within_radius(&self, point: Vec2, radius: f32, kind: SpatialKind) -> Vec<SpatialEntry> {
+        let mut found = Vec::new();
+        self.for_each_within(point, radius, kind, |entry| found.push(*entry));
+        found
+    }
+
+    /// Up to `k` entries of `kind` within `max_radius` of `point`, closest first.
+    pub fn nearest_k(
+        &self,
+        point: Vec2,
+        k: usize,
+        max_radius: f32,
+        kind: SpatialKind,
+    ) -> Vec<SpatialEntry> {
+        let mut found = self.within_radius(point, max_radius, kind);
+        found.sort_by(|a, b| {
+            a.position
+                .distance_squared(point)
+                .total_cmp(&b.position.distance_squared(point))
+        });
+        found.truncate(k);
+        found
+    }
+
+    /// The closest entry of `kind` within `max_radius` of `point`.
+    pub fn nearest(&self, point: Vec2, max_radius: f32, kind: SpatialKind) -> Option<SpatialEntry> {
+        self.nearest_where(point, max_radius, kind, |_| true)
+    }
+
+    /// The closest entry of `kind` within `max_radius` of `point` that passes `filter`.
+    pub fn nearest_where(
+        &self,
+        point: Vec2,
+        max_radius: f32,
+        kind: SpatialKind,
+        filter: impl Fn(&SpatialEntry) -> bool,
+    ) -> Option<SpatialEntry> {
+        let mut best: Option<(f32, SpatialEntry)> = None;
+        self.for_each_within(point, max_radius, kind, |entry| {
+            if !filter(entry) {
+                return;
+            }
+            let dist_sq = entry.position.distance_squared(point);
+            if best.is_none_or(|(best_sq, _)| dist_sq < best_sq) {
+                best = Some((dist_sq, *entry));
+            }
+        });
+        best.map(|(_, entry)| entry)
+    }
+}
+
+pub struct SpatialPlugin;
+
+impl Plugin for SpatialPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>()
+            .add_systems(FixedPreUpdate, rebuild_spatial_index);
+
+        #[cfg(feature = "spatial-bench")]
+        app.add_systems(Startup, bench::spawn_bench_bodies)
+            .add_systems(Update, bench::run_spatial_bench);
+    }
+}
+
+fn rebuild_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    enemy_query: Query<(Entity, &Transform), With<Enemy>>,
+    pickup_query: Query<(Entity, &Transform), Or<(With<ExperienceGem>, With<LootDrop>)>>,
+) {
+    index.clear();
+    for (entity, transform) in enemy_query.iter() {
+        index.insert(entity, transform.translation.truncate(), SpatialKind::Enemy);
+    }
+    for (entity, transform) in pickup_query.iter() {
+        index.insert(entity, transform.translation.truncate(), SpatialKind::Pickup);
+    }
+}
+
+/// Compares `SpatialIndex` against rapier shape queries with 5k bodies.
+/// Run with `cargo run --release --features spatial-bench`.
+#[cfg(feature = "spatial-bench")]
+mod bench {
+    use std::time::{Duration, Instant};
+
+    use bevy::prelude::*;
+    use bevy_rapier2d::prelude::*;
+    use rand::Rng;
+
+    use super::{SpatialIndex, SpatialKind};
+
+    const BODY_COUNT: usize = 5_000;
+    const QUERY_COUNT: usize = 1_000;
+    const QUERY_RADIUS: f32 = 150.0;
+    const ARENA_HALF_EXTENT: f32 = 2_000.0;
+
+    #[derive(Component)]
+    pub struct BenchBody;
+
+    pub fn spawn_bench_bodies(mut commands: Commands) {
+        let mut rng = rand::thread_rng();
+        for _ in 0..BODY_COUNT {
+            let x = rng.gen_range(-ARENA_HALF_EXTENT..ARENA_HALF_EXTENT);
+            let y = rng.gen_range(-ARENA_HALF_EXTENT..ARENA_HALF_EXTENT);
+            commands.spawn((
+                BenchBody,
+                TransformBundle::from_transform(Transform::from_xyz(x, y, 0.0)),
+                Collider::ball(10.0),
+                Sensor,
+            ));
+        }
+    }
+
+    /// Waits a few frames so rapier has built its query pipeline, then runs once.
+    pub fn run_spatial_bench(
+        mut commands: Commands,
+        mut frames: Local<u32>,
+        rapier_context: Res<RapierContext>,
+        bodies: Query<(Entity, &Transform), With<BenchBody>>,
+    ) {
+        *frames += 1;
+        if *frames != 3 {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        let points: Vec<Vec2> = (0..QUERY_COUNT)
+            .map(|_| {
+                Vec2::new(
+                    rng.gen_range(-ARENA_HALF_EXTENT..ARENA_HALF_EXTENT),
+                    rng.gen_range(-ARENA_HALF_EXTENT..ARENA_HALF_EXTENT),
+                )
+            })
+            .collect();
+
+        let start = Instant::now();
+        let mut index = SpatialIndex::default();
+        for (entity, transform) in bodies.iter() {
+            index.insert(entity, transform.translation.truncate(), SpatialKind::Enemy);
+        }
+        let build_time = start.elapsed();
+
+        let start = Instant::now();
+        let mut grid_hits = 0;
+        for point in &points {
+            index.for_each_within(*point, QUERY_RADIUS, SpatialKind::Enemy, |_| grid_hits += 1);
+        }
+        let grid_time = start.elapsed();
+
+        let shape = Collider::ball(QUERY_RADIUS);
+        let start = Instant::now();
+        let mut rapier_hits = 0;
+        for point in &points {
+            rapier_context.intersections_with_shape(
+                *point,
+                0.0,
+                &shape,
+                QueryFilter::default(),
+                |_| {
+                    rapier_hits += 1;
+                    true
+                },
+            );
+        }
+        let rapier_time = start.elapsed();
+
+        let per_query = |total: Duration| total.as_secs_f64() * 1e6 / QUERY_COUNT as f64;
+        println!(
+            "spatial bench: {} bodies, {} queries (r={})",
+            index.len(),
+            QUERY_COUNT,
+            QUERY_RADIUS
+        );
+        println!(
+            "  hash grid: build {:?}, {:.2} us/query, {} hits",
+            build_time,
+            per_query(grid_time),
+            grid_hits
+        );
+        println!(
+            "  rapier:    {:.2} us/query, {} hits",
+            per_query(rapier_time),
+            rapier_hits
+        );
+
+        for (entity, _) in bodies.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
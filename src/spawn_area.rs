@@ -0,0 +1,110 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{movement::Velocity, player::Player};
+
+/// Tuning for where off-screen spawns are placed.
+#[derive(Resource, Debug, Clone)]
+pub struct SpawnPlacement {
+    /// Extra distance beyond the camera view edge, in world units.
+    pub margin: f32,
+    /// Chance (0..=1) that a spawn is placed ahead of the player's movement
+    /// instead of in a uniformly random direction.
+    pub movement_bias: f32,
+    /// Half-angle in radians of the cone ahead of the player used for biased spawns.
+    pub bias_spread: f32,
+}
+
+impl Default for SpawnPlacement {
+    fn default() -> Self {
+        Self {
+            margin: 32.0,
+            movement_bias: 0.0,
+            bias_spread: std::f32::consts::FRAC_PI_4,
+        }
+    }
+}
+
+/// Snapshot of the visible world rectangle used to place spawns just off-screen.
+#[derive(Debug, Clone, Copy)]
+pub struct SpawnArea {
+    pub view: Rect,
+    pub margin: f32,
+}
+
+impl SpawnArea {
+    pub fn center(&self) -> Vec2 {
+        self.view.center()
+    }
+
+    /// Point outside the view in `direction` from its center, far enough that
+    /// an entity of `size` is fully hidden.
+    pub fn edge_point(&self, direction: Vec2, size: f32) -> Vec2 {
+        let direction = direction.normalize_or(Vec2::X);
+        let half = self.view.half_size() + Vec2::splat(self.margin + size);
+        // Distance along `direction` to the expanded rectangle's boundary.
+        let tx = if direction.x != 0.0 { half.x / direction.x.abs() } else { f32::INFINITY };
+        let ty = if direction.y != 0.0 { half.y / direction.y.abs() } else { f32::INFINITY };
+        self.center() + direction * tx.min(ty)
+    }
+
+    /// Radius of a circle around the view center that lies fully off-screen.
+    pub fn ring_radius(&self, size: f32) -> f32 {
+        self.view.half_size().length() + self.margin + size
+    }
+}
+
+/// System parameter bundling what is needed to place spawns around the camera.
+#[derive(SystemParam)]
+pub struct SpawnView<'w, 's> {
+    placement: Res<'w, SpawnPlacement>,
+    cameras: Query<
+        'w,
+        's,
+        (&'static Camera, &'static GlobalTransform, &'static OrthographicProjection),
+        With<Camera2d>,
+    >,
+    player: Query<'w, 's, &'static Velocity, With<Player>>,
+}
+
+impl<'w, 's> SpawnView<'w, 's> {
+    /// Visible area of the first active 2D camera, or `None` when there is no camera.
+    pub fn area(&self) -> Option<SpawnArea> {
+        let (_, transform, projection) = self
+            .cameras
+            .iter()
+            .filter(|(camera, _, _)| camera.is_active)
+            .min_by_key(|(camera, _, _)| camera.order)?;
+        // The projection area is already in world units and includes zoom.
+        let center = transform.translation().truncate();
+        Some(SpawnArea {
+            view: Rect::from_corners(center + projection.area.min, center + projection.area.max),
+            margin: self.placement.margin,
+        })
+    }
+
+    /// Spawn direction from the view center, biased towards where the player is heading.
+    pub fn random_direction(&self, rng: &mut impl Rng) -> Vec2 {
+        let heading = self
+            .player
+            .get_single()
+            .map(|velocity| velocity.0.normalize_or_zero())
+            .unwrap_or(Vec2::ZERO);
+
+        let bias = self.placement.movement_bias.clamp(0.0, 1.0) as f64;
+        let angle = if heading != Vec2::ZERO && rng.gen_bool(bias) {
+            let spread = self.placement.bias_spread;
+            heading.to_angle() + rng.gen_range(-spread..=spread)
+        } else {
+            rng.gen_range(0.0..std::f32::consts::TAU)
+        };
+        Vec2::from_angle(angle)
+    }
+
+    /// Random point just outside the view for an entity of `size`.
+    pub fn random_position(&self, size: f32, rng: &mut impl Rng) -> Option<Vec2> {
+        let area = self.area()?;
+        Some(area.edge_point(self.random_direction(rng), size))
+    }
+}
@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    behavior::{EnemyBehavior, EnemyBehaviorAppExt},
+    difficulty::Difficulty,
+    enemy::{spawn_enemy, CurrentEnemyStats, EnemyType, SpawnBudget},
+    player::Player,
+    GameplaySet,
+};
+
+const PREFERRED_RANGE: f32 = 350.0;
+// Summoners only reposition when they drift this far from the preferred range.
+const RANGE_TOLERANCE: f32 = 60.0;
+const SUMMON_INTERVAL: f32 = 4.0;
+const MINIONS_PER_SUMMON: u32 = 3;
+const MAX_MINIONS: usize = 9;
+const SUMMON_RADIUS: f32 = 60.0;
+
+/// Summoning state of an enemy with `EnemyBehavior::SUMMON`.
+#[derive(Component)]
+pub struct Summoner {
+    pub timer: Timer,
+    pub max_minions: usize,
+    minions: Vec<Entity>,
+}
+
+impl Default for Summoner {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(SUMMON_INTERVAL, TimerMode::Repeating),
+            max_minions: MAX_MINIONS,
+            minions: Vec::new(),
+        }
+    }
+}
+
+/// Enemy summoned by `summoner`. Despawned when its summoner dies.
+#[derive(Component)]
+pub struct Minion {
+    pub summoner: Entity,
+}
+
+pub struct SummonerPlugin;
+
+impl Plugin for SummonerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_enemy_behavior(
+            EnemyBehavior::SUMMON,
+            (init_summoners, summon_behavior).chain(),
+        )
+        .add_systems(FixedUpdate, despawn_orphaned_minions.in_set(GameplaySet));
+    }
+}
+
+fn init_summoners(
+    mut commands: Commands,
+    query: Query<(Entity, &EnemyBehavior), (Added<EnemyBehavior>, Without<Summoner>)>,
+) {
+    for (entity, behavior) in query.iter() {
+        if *behavior == EnemyBehavior::SUMMON {
+            commands.entity(entity).insert(Summoner::default());
+        }
+    }
+}
+
+fn summon_behavior(
+    mut commands: Commands,
+    time: Res<Time>,
+    difficulty: Difficulty,
+    mut budget: ResMut<SpawnBudget>,
+    player_query: Query<&Transform, With<Player>>,
+    minion_query: Query<(), With<Minion>>,
+    mut summoner_query: Query<
+        (
+            Entity,
+            &EnemyBehavior,
+            &Transform,
+            &mut Velocity,
+            &CurrentEnemyStats,
+            &mut Summoner,
+        ),
+        Without<Player>,
+    >,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+    let minion_stats = difficulty.scale_stats(EnemyType::Minion.get_stats());
+
+    for (entity, behavior, transform, mut velocity, stats, mut summoner) in summoner_query.iter_mut() {
+        if *behavior != EnemyBehavior::SUMMON {
+            continue;
+        }
+        let position = transform.translation.truncate();
+
+        // Keep at range: approach when too far, back off when too close, otherwise circle.
+        let to_player = player_pos - position;
+        let distance = to_player.length();
+        let toward = to_player.normalize_or_zero();
+        let direction = if distance > PREFERRED_RANGE + RANGE_TOLERANCE {
+            toward
+        } else if distance < PREFERRED_RANGE - RANGE_TOLERANCE {
+            -toward
+        } else {
+            toward.perp()
+        };
+        velocity.linvel = direction * stats.speed;
+
+        summoner.minions.retain(|minion| minion_query.contains(*minion));
+        summoner.timer.tick(time.delta());
+        if !summoner.timer.just_finished() {
+            continue;
+        }
+
+        let count = summoner
+            .max_minions
+            .saturating_sub(summoner.minions.len())
+            .min(MINIONS_PER_SUMMON as usize);
+        for i in 0..count {
+            let angle = i as f32 / count as f32 * std::f32::consts::TAU;
+            let offset = Vec2::new(angle.cos(), angle.sin()) * SUMMON_RADIUS;
+            let Some(minion) = spawn_enemy(
+                &mut commands,
+                &mut budget,
+                EnemyType::Minion,
+                minion_stats,
+                position + offset,
+            ) else {
+                break;
+            };
+            commands.entity(minion).insert(Minion { summoner: entity });
+            summoner.minions.push(minion);
+        }
+    }
+}
+
+fn despawn_orphaned_minions(
+    mut commands: Commands,
+    minion_query: Query<(Entity, &Minion)>,
+    summoner_query: Query<(), With<Summoner>>,
+) {
+    for (entity, minion) in minion_query.iter() {
+        if !summoner_query.contains(minion.summoner) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
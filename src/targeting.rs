@@ -0,0 +1,93 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+
+use crate::{
+    components::Health,
+    enemy::Enemy,
+    rng::{GameRng, RngStream},
+    spatial::{SpatialEntry, SpatialIndex, SpatialKind},
+};
+
+/// How an auto-aimed weapon chooses what to shoot at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetingStrategy {
+    #[default]
+    Nearest,
+    LowestHealth,
+    HighestHealth,
+    RandomInRange,
+    /// Fire wherever the player is facing, target or not.
+    Facing,
+}
+
+/// Enemy lookups shared by every auto-aimed weapon.
+#[derive(SystemParam)]
+pub struct Targeting<'w, 's> {
+    index: Res<'w, SpatialIndex>,
+    game_rng: ResMut<'w, GameRng>,
+    health_query: Query<'w, 's, &'static Health, With<Enemy>>,
+}
+
+impl Targeting<'_, '_> {
+    /// The enemy within `range` of `origin` picked by `strategy`.
+    /// `TargetingStrategy::Facing` doesn't pick enemies and always returns `None`.
+    pub fn pick(
+        &mut self,
+        strategy: TargetingStrategy,
+        origin: Vec2,
+        range: f32,
+    ) -> Option<SpatialEntry> {
+        let health = |entry: &SpatialEntry| {
+            self.health_query
+                .get(entry.entity)
+                .map_or(0.0, |health| health.value)
+        };
+        match strategy {
+            TargetingStrategy::Nearest => self.index.nearest(origin, range, SpatialKind::Enemy),
+            TargetingStrategy::LowestHealth => self
+                .index
+                .within_radius(origin, range, SpatialKind::Enemy)
+                .into_iter()
+                .min_by(|a, b| health(a).total_cmp(&health(b))),
+            TargetingStrategy::HighestHealth => self
+                .index
+                .within_radius(origin, range, SpatialKind::Enemy)
+                .into_iter()
+                .max_by(|a, b| health(a).total_cmp(&health(b))),
+            TargetingStrategy::RandomInRange => self
+                .index
+                .within_radius(origin, range, SpatialKind::Enemy)
+                .choose(self.game_rng.stream(RngStream::Weapons))
+                .copied(),
+            TargetingStrategy::Facing => None,
+        }
+    }
+
+    /// Direction to fire in from `origin`, or `None` when there is nothing to aim at.
+    pub fn aim(
+        &mut self,
+        strategy: TargetingStrategy,
+        origin: Vec2,
+        range: f32,
+        facing: Vec2,
+    ) -> Option<Vec2> {
+        if strategy == TargetingStrategy::Facing {
+            return facing.try_normalize();
+        }
+        self.pick(strategy, origin, range)
+            .and_then(|target| (target.position - origin).try_normalize())
+    }
+
+    /// The closest enemy within `range` of `origin` that isn't in `exclude`.
+    pub fn nearest_excluding(
+        &self,
+        origin: Vec2,
+        range: f32,
+        exclude: &[Entity],
+    ) -> Option<SpatialEntry> {
+        self.index.nearest_where(origin, range, SpatialKind::Enemy, |entry| {
+            !exclude.contains(&entry.entity)
+        })
+    }
+}
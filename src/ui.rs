@@ -1,30 +1,78 @@
 use bevy::prelude::*;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
 
-use crate::{experience::PlayerStats, GameState};
+use crate::{
+    difficulty::RunTime,
+    experience::PlayerStats,
+    loot::Wallet,
+    player::Player,
+    projectile::ProjectileModifiers,
+    rng::{GameRng, RngStream},
+    weapon::{Loadout, WeaponId, WeaponRegistry},
+    GameState,
+};
+
+const UPGRADE_CHOICES: usize = 3;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Upgrade {
-    AddOrbital,
+    LevelUpWeapon(WeaponId),
+    NewWeapon(WeaponId),
     IncreaseDamage,
     IncreaseSpeed,
+    ProjectilePierce,
+    ProjectileBounce,
+    ProjectileHoming,
+    ProjectileReach,
 }
 
 impl Upgrade {
-    fn all() -> Vec<Self> {
+    /// Weapon progression: level ups for owned weapons that are not maxed out,
+    /// and every registered weapon the player doesn't own yet.
+    fn weapon_upgrades(registry: &WeaponRegistry, loadout: &Loadout) -> Vec<Self> {
+        let mut upgrades = Vec::new();
+        for definition in registry.iter() {
+            match loadout.get(definition.id) {
+                Some(weapon) if weapon.level < definition.max_level() => {
+                    upgrades.push(Upgrade::LevelUpWeapon(definition.id));
+                }
+                Some(_) => {}
+                None => upgrades.push(Upgrade::NewWeapon(definition.id)),
+            }
+        }
+        upgrades
+    }
+
+    /// Global upgrades, offered when there aren't enough weapon upgrades left.
+    fn global_upgrades() -> Vec<Self> {
         vec![
-            Upgrade::AddOrbital,
             Upgrade::IncreaseDamage,
             Upgrade::IncreaseSpeed,
+            Upgrade::ProjectilePierce,
+            Upgrade::ProjectileBounce,
+            Upgrade::ProjectileHoming,
+            Upgrade::ProjectileReach,
         ]
     }
 
-    fn get_description(&self) -> &str {
+    fn get_description(&self, registry: &WeaponRegistry, loadout: &Loadout) -> String {
+        let name = |id: WeaponId| registry.get(id).map_or(id.0, |definition| definition.name);
         match self {
-            Upgrade::AddOrbital => "Add another orbiting weapon",
-            Upgrade::IncreaseDamage => "Increase weapon damage by 20%",
-            Upgrade::IncreaseSpeed => "Increase movement speed by 10%",
+            Upgrade::LevelUpWeapon(id) => {
+                let next_level = loadout.get(*id).map_or(1, |weapon| weapon.level + 1);
+                let changes = registry
+                    .get(*id)
+                    .map(|definition| definition.describe_level(next_level))
+                    .unwrap_or_default();
+                format!("{} -> level {}: {}", name(*id), next_level, changes)
+            }
+            Upgrade::NewWeapon(id) => format!("New weapon: {}", name(*id)),
+            Upgrade::IncreaseDamage => "Increase weapon damage by 20%".to_string(),
+            Upgrade::IncreaseSpeed => "Increase movement speed by 10%".to_string(),
+            Upgrade::ProjectilePierce => "Projectiles pierce one more enemy".to_string(),
+            Upgrade::ProjectileBounce => "Projectiles bounce to one more enemy".to_string(),
+            Upgrade::ProjectileHoming => "Projectiles home in on enemies".to_string(),
+            Upgrade::ProjectileReach => "Projectiles last and travel 20% longer".to_string(),
         }
     }
 }
@@ -35,12 +83,26 @@ struct LevelUpScreen;
 #[derive(Component)]
 struct UpgradeButton(Upgrade);
 
+#[derive(Component)]
+struct PauseScreen;
+
+#[derive(Component)]
+struct GameOverScreen;
+
+#[derive(Component)]
+struct GoldText;
+
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(OnEnter(GameState::LevelUp), setup_level_up_screen)
             .add_systems(OnExit(GameState::LevelUp), teardown_level_up_screen)
+            .add_systems(OnEnter(GameState::Paused), setup_pause_screen)
+            .add_systems(OnExit(GameState::Paused), teardown_pause_screen)
+            .add_systems(OnEnter(GameState::GameOver), setup_game_over_screen)
+            .add_systems(Startup, setup_hud)
+            .add_systems(Update, update_gold_text)
             .add_systems(
                 Update,
                 upgrade_button_interaction.run_if(in_state(GameState::LevelUp)),
@@ -48,13 +110,22 @@ impl Plugin for UiPlugin {
     }
 }
 
-fn setup_level_up_screen(mut commands: Commands) {
-    let mut rng = thread_rng();
-    let all_upgrades = Upgrade::all();
-    let chosen_upgrades = all_upgrades
-        .choose_multiple(&mut rng, 3)
+fn setup_level_up_screen(
+    mut commands: Commands,
+    mut game_rng: ResMut<GameRng>,
+    weapon_registry: Res<WeaponRegistry>,
+    loadout_query: Query<&Loadout, With<Player>>,
+) {
+    let Ok(loadout) = loadout_query.get_single() else {
+        return;
+    };
+    let rng = game_rng.stream(RngStream::Upgrades);
+    let mut chosen_upgrades = Upgrade::weapon_upgrades(&weapon_registry, loadout)
+        .choose_multiple(rng, UPGRADE_CHOICES)
         .cloned()
         .collect::<Vec<_>>();
+    let missing = UPGRADE_CHOICES - chosen_upgrades.len();
+    chosen_upgrades.extend(Upgrade::global_upgrades().choose_multiple(rng, missing).cloned());
 
     commands
         .spawn((
@@ -88,7 +159,8 @@ fn setup_level_up_screen(mut commands: Commands) {
                     .spawn((
                         ButtonBundle {
                             style: Style {
-                                width: Val::Px(400.0),
+                                // Wide enough for "Weapon -> level N: change" lines.
+                                width: Val::Px(560.0),
                                 height: Val::Px(100.0),
                                 justify_content: JustifyContent::Center,
                                 align_items: AlignItems::Center,
@@ -101,9 +173,9 @@ fn setup_level_up_screen(mut commands: Commands) {
                     ))
                     .with_children(|parent| {
                         parent.spawn(TextBundle::from_section(
-                            upgrade.get_description(),
+                            upgrade.get_description(&weapon_registry, loadout),
                             TextStyle {
-                                font_size: 30.0,
+                                font_size: 24.0,
                                 color: Color::WHITE,
                                 ..default()
                             },
@@ -122,26 +194,145 @@ fn teardown_level_up_screen(
     }
 }
 
+fn setup_pause_screen(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.6).into(),
+                ..default()
+            },
+            PauseScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Paused",
+                TextStyle {
+                    font_size: 80.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+        });
+}
+
+fn teardown_pause_screen(mut commands: Commands, query: Query<Entity, With<PauseScreen>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn setup_game_over_screen(
+    mut commands: Commands,
+    run_time: Res<RunTime>,
+    player_stats: Res<PlayerStats>,
+    wallet: Res<Wallet>,
+) {
+    let seconds = run_time.0.elapsed_secs() as u32;
+    let summary = [
+        format!("Survived: {}:{:02}", seconds / 60, seconds % 60),
+        format!("Level: {}", player_stats.level),
+        format!("Gold: {}", wallet.gold),
+    ];
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    align_items: AlignItems::Center,
+                    justify_content: JustifyContent::Center,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.8).into(),
+                ..default()
+            },
+            GameOverScreen,
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Game Over",
+                TextStyle {
+                    font_size: 80.0,
+                    color: Color::WHITE,
+                    ..default()
+                },
+            ));
+            for line in summary {
+                parent.spawn(TextBundle::from_section(
+                    line,
+                    TextStyle {
+                        font_size: 30.0,
+                        color: Color::WHITE,
+                        ..default()
+                    },
+                ));
+            }
+        });
+}
+
+fn setup_hud(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "Gold: 0",
+            TextStyle {
+                font_size: 24.0,
+                color: Color::srgb(1.0, 0.84, 0.0),
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            right: Val::Px(10.0),
+            ..default()
+        }),
+        GoldText,
+    ));
+}
+
+fn update_gold_text(wallet: Res<Wallet>, mut query: Query<&mut Text, With<GoldText>>) {
+    if !wallet.is_changed() {
+        return;
+    }
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("Gold: {}", wallet.gold);
+    }
+}
+
 fn upgrade_button_interaction(
     mut interaction_query: Query<
         (&Interaction, &UpgradeButton, &mut BackgroundColor),
         (Changed<Interaction>, With<Button>),
     >,
     mut player_stats: ResMut<PlayerStats>,
+    mut projectile_modifiers: ResMut<ProjectileModifiers>,
     mut next_state: ResMut<NextState<GameState>>,
-    mut commands: Commands, // To spawn new weapon
-    player_query: Query<Entity, With<crate::player::Player>>,
+    weapon_registry: Res<WeaponRegistry>,
+    mut loadout_query: Query<&mut Loadout, With<Player>>,
 ) {
     for (interaction, button, mut color) in interaction_query.iter_mut() {
         match *interaction {
             Interaction::Pressed => {
                 *color = Color::srgb(0.35, 0.75, 0.35).into();
-                apply_upgrade(
-                    button.0,
-                    &mut player_stats,
-                    &mut commands,
-                    &player_query,
-                );
+                if let Ok(mut loadout) = loadout_query.get_single_mut() {
+                    apply_upgrade(
+                        button.0,
+                        &mut player_stats,
+                        &mut projectile_modifiers,
+                        &weapon_registry,
+                        &mut loadout,
+                    );
+                }
                 next_state.set(GameState::Gameplay);
             }
             Interaction::Hovered => {
@@ -157,16 +348,16 @@ fn upgrade_button_interaction(
 fn apply_upgrade(
     upgrade: Upgrade,
     player_stats: &mut ResMut<PlayerStats>,
-    commands: &mut Commands,
-    player_query: &Query<Entity, With<crate::player::Player>>,
+    projectile_modifiers: &mut ProjectileModifiers,
+    weapon_registry: &WeaponRegistry,
+    loadout: &mut Loadout,
 ) {
     match upgrade {
-        Upgrade::AddOrbital => {
-            if let Ok(player_entity) = player_query.get_single() {
-                let new_weapon = crate::weapon::spawn_orbital_weapon(commands);
-                commands.entity(player_entity).add_child(new_weapon);
-                player_stats.orbital_count += 1;
-            }
+        Upgrade::LevelUpWeapon(id) => {
+            loadout.level_up(id, weapon_registry);
+        }
+        Upgrade::NewWeapon(id) => {
+            loadout.add(id, weapon_registry);
         }
         Upgrade::IncreaseDamage => {
             player_stats.damage_multiplier += 0.2;
@@ -174,6 +365,19 @@ fn apply_upgrade(
         Upgrade::IncreaseSpeed => {
             player_stats.speed_multiplier += 0.1;
         }
+        Upgrade::ProjectilePierce => {
+            projectile_modifiers.extra_pierce += 1;
+        }
+        Upgrade::ProjectileBounce => {
+            projectile_modifiers.extra_bounces += 1;
+        }
+        Upgrade::ProjectileHoming => {
+            projectile_modifiers.homing_upgrades += 1;
+        }
+        Upgrade::ProjectileReach => {
+            projectile_modifiers.lifetime_multiplier += 0.2;
+            projectile_modifiers.range_multiplier += 0.2;
+        }
     }
     println!("Applied upgrade: {:?}", upgrade);
 }
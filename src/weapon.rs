@@ -1,53 +1,479 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
+use crate::GameplaySet;
+
 const ORBIT_DISTANCE: f32 = 100.0;
 const ORBIT_SPEED: f32 = 5.0;
 const WEAPON_SIZE: f32 = 25.0;
+// Orbitals beyond this many start a new, wider ring.
+const ORBITALS_PER_RING: u32 = 3;
+const RING_SPACING: f32 = 50.0;
+
+/// Identifies a weapon kind in the `WeaponRegistry`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WeaponId(pub &'static str);
+
+impl WeaponId {
+    pub const ORBITAL: Self = Self("orbital");
+    pub const BOLT: Self = Self("bolt");
+    pub const CHAIN_LIGHTNING: Self = Self("chain_lightning");
+    pub const AURA: Self = Self("aura");
+    pub const WHIP: Self = Self("whip");
+    pub const BOOMERANG: Self = Self("boomerang");
+    pub const MINE: Self = Self("mine");
+}
+
+/// Numbers every weapon kind can be tuned with. Each kind uses the ones that
+/// make sense for it and ignores the rest.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeaponStats {
+    pub damage: f32,
+    /// Seconds between attacks, for weapons that fire on a cooldown.
+    pub cooldown: f32,
+    /// Number of projectiles, orbitals, etc.
+    pub amount: u32,
+    /// Multiplier on size and radius.
+    pub area: f32,
+    /// Multiplier on travel or rotation speed.
+    pub speed: f32,
+    /// Fraction of movement speed taken from enemies hit, for weapons that slow.
+    pub slow: f32,
+}
+
+impl Default for WeaponStats {
+    fn default() -> Self {
+        Self {
+            damage: 10.0,
+            cooldown: 1.0,
+            amount: 1,
+            area: 1.0,
+            speed: 1.0,
+            slow: 0.0,
+        }
+    }
+}
+
+/// One entry of a weapon's level table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StatChange {
+    /// Flat damage added per hit.
+    Damage(f32),
+    Amount(u32),
+    /// Relative changes, e.g. `Area(0.2)` is +20% area and `Cooldown(-0.1)` is -10% cooldown.
+    Area(f32),
+    Cooldown(f32),
+    Speed(f32),
+    /// Added to `WeaponStats::slow`, e.g. `Slow(0.4)` takes another 40% of enemy speed.
+    Slow(f32),
+}
+
+impl StatChange {
+    pub fn apply(&self, stats: &mut WeaponStats) {
+        match *self {
+            StatChange::Damage(value) => stats.damage += value,
+            StatChange::Amount(value) => stats.amount += value,
+            StatChange::Area(fraction) => stats.area *= 1.0 + fraction,
+            StatChange::Cooldown(fraction) => stats.cooldown *= 1.0 + fraction,
+            StatChange::Speed(fraction) => stats.speed *= 1.0 + fraction,
+            StatChange::Slow(fraction) => stats.slow = (stats.slow + fraction).min(1.0),
+        }
+    }
+
+    /// Short player-facing text such as "+1 bolt" or "-10% cooldown".
+    pub fn describe(&self, amount_label: &str) -> String {
+        let percent = |fraction: f32| (fraction * 100.0).round() as i32;
+        match *self {
+            StatChange::Damage(value) => format!("+{value} damage"),
+            StatChange::Amount(value) => format!("+{value} {amount_label}"),
+            StatChange::Area(fraction) => format!("{:+}% area", percent(fraction)),
+            StatChange::Cooldown(fraction) => format!("{:+}% cooldown", percent(fraction)),
+            StatChange::Speed(fraction) => format!("{:+}% speed", percent(fraction)),
+            StatChange::Slow(fraction) => format!("{:+}% slow", percent(fraction)),
+        }
+    }
+}
+
+/// Everything the game needs to know about one weapon kind.
+pub struct WeaponDefinition {
+    pub id: WeaponId,
+    pub name: &'static str,
+    /// What `WeaponStats::amount` counts for this weapon, used in level descriptions.
+    pub amount_label: &'static str,
+    pub base_stats: WeaponStats,
+    /// Changes applied on reaching each level, starting with level 2. The weapon
+    /// maxes out once the table runs out.
+    pub levels: &'static [&'static [StatChange]],
+    /// Spawn the weapon's persistent entities (orbitals, auras, ...) for `owner`.
+    /// Called when the weapon is equipped and again after every level up, after
+    /// the previous entities tagged with `WeaponPart` have been removed. Weapons
+    /// that only fire on a cooldown can leave this empty.
+    pub spawn: fn(&mut Commands, Entity, &WeaponInstance),
+}
+
+impl WeaponDefinition {
+    pub fn max_level(&self) -> u32 {
+        self.levels.len() as u32 + 1
+    }
+
+    /// Changes applied on reaching `level`, empty for level 1 and past the max level.
+    pub fn changes_for(&self, level: u32) -> &'static [StatChange] {
+        level
+            .checked_sub(2)
+            .and_then(|index| self.levels.get(index as usize))
+            .copied()
+            .unwrap_or(&[])
+    }
+
+    /// Describe the changes for reaching `level`, e.g. "+1 bolt, -15% cooldown".
+    pub fn describe_level(&self, level: u32) -> String {
+        self.changes_for(level)
+            .iter()
+            .map(|change| change.describe(self.amount_label))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// All weapon kinds the player can own.
+#[derive(Resource, Default)]
+pub struct WeaponRegistry {
+    definitions: HashMap<WeaponId, WeaponDefinition>,
+    // Registration order, so menus list weapons consistently.
+    order: Vec<WeaponId>,
+}
+
+impl WeaponRegistry {
+    pub fn get(&self, id: WeaponId) -> Option<&WeaponDefinition> {
+        self.definitions.get(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WeaponDefinition> {
+        self.order.iter().map(|id| &self.definitions[id])
+    }
+}
+
+/// A weapon owned by the player at a given level.
+#[derive(Debug, Clone)]
+pub struct WeaponInstance {
+    pub id: WeaponId,
+    pub level: u32,
+    pub stats: WeaponStats,
+}
+
+/// The weapons an entity (the player) currently owns.
+#[derive(Component, Default)]
+pub struct Loadout {
+    weapons: Vec<WeaponInstance>,
+    // Weapons whose spawned entities need rebuilding.
+    dirty: Vec<WeaponId>,
+}
+
+impl Loadout {
+    pub fn get(&self, id: WeaponId) -> Option<&WeaponInstance> {
+        self.weapons.iter().find(|weapon| weapon.id == id)
+    }
+
+    pub fn has(&self, id: WeaponId) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &WeaponInstance> {
+        self.weapons.iter()
+    }
+
+    /// Equip a new weapon at level 1. Returns false if it is already owned or unknown.
+    pub fn add(&mut self, id: WeaponId, registry: &WeaponRegistry) -> bool {
+        let Some(definition) = registry.get(id) else {
+            return false;
+        };
+        if self.has(id) {
+            return false;
+        }
+        self.weapons.push(WeaponInstance {
+            id,
+            level: 1,
+            stats: definition.base_stats,
+        });
+        self.dirty.push(id);
+        true
+    }
+
+    /// Raise an owned weapon by one level. Returns false if it is not owned or maxed out.
+    pub fn level_up(&mut self, id: WeaponId, registry: &WeaponRegistry) -> bool {
+        let Some(definition) = registry.get(id) else {
+            return false;
+        };
+        let Some(weapon) = self.weapons.iter_mut().find(|weapon| weapon.id == id) else {
+            return false;
+        };
+        if weapon.level >= definition.max_level() {
+            return false;
+        }
+        weapon.level += 1;
+        for change in definition.changes_for(weapon.level) {
+            change.apply(&mut weapon.stats);
+        }
+        self.dirty.push(id);
+        true
+    }
+}
+
+/// Entity spawned by a weapon's `spawn` function on behalf of `owner`.
+#[derive(Component)]
+pub struct WeaponPart {
+    pub weapon: WeaponId,
+    pub owner: Entity,
+}
+
+/// Timer and stats of a weapon that attacks on a cooldown. Lives on a child
+/// of the owner next to the weapon kind's marker, see `spawn_weapon`.
+#[derive(Component)]
+pub struct WeaponCooldown {
+    timer: Timer,
+    stats: WeaponStats,
+}
+
+impl WeaponCooldown {
+    pub fn new(stats: WeaponStats) -> Self {
+        Self {
+            timer: Timer::from_seconds(stats.cooldown, TimerMode::Repeating),
+            stats,
+        }
+    }
+
+    /// Advance the cooldown. Returns true on the ticks the weapon attacks.
+    pub fn tick(&mut self, delta: Duration) -> bool {
+        self.timer.tick(delta).just_finished()
+    }
+
+    pub fn stats(&self) -> WeaponStats {
+        self.stats
+    }
+}
+
+/// `WeaponDefinition::spawn` for cooldown weapons: a child of `owner` with the
+/// marker `W` and a `WeaponCooldown`, which the weapon's systems query for.
+pub fn spawn_weapon<W: Component + Default>(
+    commands: &mut Commands,
+    owner: Entity,
+    instance: &WeaponInstance,
+) {
+    let weapon = commands
+        .spawn((W::default(), WeaponCooldown::new(instance.stats)))
+        .insert(WeaponPart {
+            weapon: instance.id,
+            owner,
+        })
+        .id();
+    commands.entity(owner).add_child(weapon);
+}
+
+/// `amount` directions fanned out evenly around `direction`, `angle` radians apart.
+pub fn spread(direction: Vec2, amount: u32, angle: f32) -> impl Iterator<Item = Vec2> {
+    let middle = (amount as f32 - 1.0) / 2.0;
+    (0..amount).map(move |i| Vec2::from_angle((i as f32 - middle) * angle).rotate(direction))
+}
 
 #[derive(Component)]
 pub struct Weapon;
 
+/// Tuning for the orbital weapon.
+#[derive(Resource, Debug, Clone)]
+pub struct OrbitalConfig {
+    /// Spin every other ring the opposite way.
+    pub counter_rotate_rings: bool,
+}
+
+impl Default for OrbitalConfig {
+    fn default() -> Self {
+        Self {
+            counter_rotate_rings: true,
+        }
+    }
+}
+
+/// One ball of the orbital weapon, circling its owner at `radius`.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Orbital {
+    /// Starting angle, spreading the orbitals of a ring evenly.
+    pub phase: f32,
+    pub radius: f32,
+    /// Radians per second; negative values spin clockwise.
+    pub angular_speed: f32,
+    /// Ring index, counting outwards from 0.
+    pub ring: u32,
+}
+
+impl Orbital {
+    pub fn offset(&self, elapsed: f32, counter_rotate: bool) -> Vec2 {
+        let direction = if counter_rotate && self.ring % 2 == 1 { -1.0 } else { 1.0 };
+        let angle = self.phase + self.angular_speed * direction * elapsed;
+        Vec2::new(angle.cos(), angle.sin()) * self.radius
+    }
+}
+
 #[derive(Component)]
 pub struct Projectile;
 
+/// Damage dealt by a `Projectile` on hit.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Damage(pub f32);
+
+/// Set containing every weapon kind's update systems. Runs in `FixedUpdate`
+/// as part of `GameplaySet`.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct WeaponSet;
+
+pub trait WeaponAppExt {
+    /// Register a weapon kind together with the systems that drive it.
+    fn register_weapon<M>(
+        &mut self,
+        definition: WeaponDefinition,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self;
+}
+
+impl WeaponAppExt for App {
+    fn register_weapon<M>(
+        &mut self,
+        definition: WeaponDefinition,
+        systems: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        let mut registry = self
+            .world_mut()
+            .get_resource_or_insert_with(WeaponRegistry::default);
+        let id = definition.id;
+        if registry.definitions.insert(id, definition).is_some() {
+            warn!("Weapon {:?} registered more than once", id.0);
+        } else {
+            registry.order.push(id);
+        }
+        self.add_systems(FixedUpdate, systems.in_set(WeaponSet))
+    }
+}
+
 pub struct WeaponPlugin;
 
 impl Plugin for WeaponPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, orbit_weapon);
+        app.init_resource::<WeaponRegistry>()
+            .init_resource::<OrbitalConfig>()
+            .configure_sets(FixedUpdate, WeaponSet.in_set(GameplaySet))
+            .add_systems(
+                FixedUpdate,
+                sync_weapon_parts.before(WeaponSet).in_set(GameplaySet),
+            )
+            .register_weapon(
+                WeaponDefinition {
+                    id: WeaponId::ORBITAL,
+                    name: "Orbital",
+                    amount_label: "orbital",
+                    base_stats: WeaponStats {
+                        damage: 10.0,
+                        ..default()
+                    },
+                    levels: &[
+                        &[StatChange::Amount(1)],
+                        &[StatChange::Area(0.25)],
+                        &[StatChange::Amount(1)],
+                        &[StatChange::Speed(0.25)],
+                        &[StatChange::Area(0.2)],
+                        &[StatChange::Amount(1)],
+                        // Fills the second ring up to two orbitals.
+                        &[
+                            StatChange::Amount(1),
+                            StatChange::Speed(0.25),
+                            StatChange::Damage(5.0),
+                        ],
+                    ],
+                    spawn: spawn_orbitals,
+                },
+                orbit_weapon,
+            );
+    }
+}
+
+/// Rebuild the spawned entities of weapons that were equipped or leveled up.
+fn sync_weapon_parts(
+    mut commands: Commands,
+    registry: Res<WeaponRegistry>,
+    mut loadout_query: Query<(Entity, &mut Loadout), Changed<Loadout>>,
+    part_query: Query<(Entity, &WeaponPart)>,
+) {
+    for (owner, mut loadout) in loadout_query.iter_mut() {
+        if loadout.dirty.is_empty() {
+            continue;
+        }
+        let dirty = std::mem::take(&mut loadout.dirty);
+        for id in dirty {
+            for (entity, part) in part_query.iter() {
+                if part.weapon == id && part.owner == owner {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+            if let (Some(definition), Some(instance)) = (registry.get(id), loadout.get(id)) {
+                (definition.spawn)(&mut commands, owner, instance);
+            }
+        }
     }
 }
 
-pub fn spawn_orbital_weapon(commands: &mut Commands) -> Entity {
+fn spawn_orbitals(commands: &mut Commands, owner: Entity, instance: &WeaponInstance) {
+    let stats = instance.stats;
+    let rings = stats.amount.div_ceil(ORBITALS_PER_RING);
+    for ring in 0..rings {
+        let first = ring * ORBITALS_PER_RING;
+        let count = (stats.amount - first).min(ORBITALS_PER_RING);
+        for index in 0..count {
+            let orbital = Orbital {
+                phase: index as f32 / count as f32 * std::f32::consts::TAU,
+                radius: (ORBIT_DISTANCE + ring as f32 * RING_SPACING) * stats.area,
+                angular_speed: ORBIT_SPEED * stats.speed,
+                ring,
+            };
+            let entity = spawn_orbital_weapon(commands, orbital, stats.damage, WEAPON_SIZE * stats.area);
+            commands.entity(entity).insert(WeaponPart {
+                weapon: WeaponId::ORBITAL,
+                owner,
+            });
+            commands.entity(owner).add_child(entity);
+        }
+    }
+}
+
+fn spawn_orbital_weapon(commands: &mut Commands, orbital: Orbital, damage: f32, size: f32) -> Entity {
     commands
         .spawn(Weapon)
         .insert(Projectile)
+        .insert(Damage(damage))
         .insert(SpriteBundle {
             sprite: Sprite {
                 color: Color::srgb(0.0, 1.0, 1.0),
-                custom_size: Some(Vec2::new(WEAPON_SIZE, WEAPON_SIZE)),
+                custom_size: Some(Vec2::new(size, size)),
                 ..default()
             },
+            transform: Transform::from_translation(orbital.offset(0.0, false).extend(1.0)),
             ..default()
         })
+        .insert(orbital)
         .insert(RigidBody::KinematicPositionBased)
-        .insert(Collider::ball(WEAPON_SIZE / 2.0))
+        .insert(Collider::ball(size / 2.0))
         .insert(Sensor)
         .id()
 }
 
+/// Orbitals are children of their owner, so this only sets the local offset.
 fn orbit_weapon(
     time: Res<Time>,
-    mut weapon_query: Query<(&Parent, &mut Transform), With<Weapon>>,
-    player_query: Query<&Transform, (With<crate::player::Player>, Without<Weapon>)>,
+    config: Res<OrbitalConfig>,
+    mut weapon_query: Query<(&Orbital, &mut Transform)>,
 ) {
-    for (parent, mut weapon_transform) in weapon_query.iter_mut() {
-        if let Ok(player_transform) = player_query.get(parent.get()) {
-            let angle = ORBIT_SPEED * time.elapsed_seconds();
-            let new_pos = Vec2::new(angle.cos(), angle.sin()) * ORBIT_DISTANCE;
-
-            weapon_transform.translation = player_transform.translation + Vec3::new(new_pos.x, new_pos.y, 1.0);
-        }
+    let elapsed = time.elapsed_seconds();
+    for (orbital, mut transform) in weapon_query.iter_mut() {
+        transform.translation = orbital.offset(elapsed, config.counter_rotate_rings).extend(1.0);
     }
 }
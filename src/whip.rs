@@ -0,0 +1,134 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    movement::Facing,
+    player::Player,
+    projectile::Lifetime,
+    weapon::{
+        spawn_weapon, Damage, Projectile, StatChange, WeaponAppExt, WeaponCooldown,
+        WeaponDefinition, WeaponId, WeaponStats,
+    },
+};
+
+const WHIP_LENGTH: f32 = 140.0;
+const WHIP_WIDTH: f32 = 30.0;
+// Gap between the player and the start of the lash.
+const WHIP_OFFSET: f32 = 10.0;
+const LASH_DURATION: f32 = 0.2;
+// Radians each lash sweeps through, centered on the direction it is aimed.
+const SWEEP_ARC: f32 = 2.0;
+
+/// Marks the whip weapon's `WeaponCooldown`.
+#[derive(Component, Default)]
+pub struct WhipWielder;
+
+/// A lash sweeping from `start` through `arc` radians around its owner over
+/// its lifetime, `reach` away from them.
+#[derive(Component)]
+pub struct Lash {
+    start: f32,
+    arc: f32,
+    reach: f32,
+    timer: Timer,
+}
+
+impl Lash {
+    fn transform(&self) -> Transform {
+        let angle = self.start + self.arc * self.timer.fraction();
+        let center = Vec2::from_angle(angle) * self.reach;
+        Transform::from_xyz(center.x, center.y, 1.0).with_rotation(Quat::from_rotation_z(angle))
+    }
+}
+
+pub struct WhipPlugin;
+
+impl Plugin for WhipPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_weapon(
+            WeaponDefinition {
+                id: WeaponId::WHIP,
+                name: "Whip",
+                amount_label: "lash",
+                base_stats: WeaponStats {
+                    damage: 12.0,
+                    cooldown: 1.3,
+                    ..default()
+                },
+                levels: &[
+                    &[StatChange::Damage(4.0)],
+                    &[StatChange::Area(0.2)],
+                    // A second lash behind the player.
+                    &[StatChange::Amount(1)],
+                    &[StatChange::Damage(4.0)],
+                    &[StatChange::Area(0.2)],
+                    &[StatChange::Cooldown(-0.1)],
+                    &[StatChange::Damage(4.0)],
+                ],
+                spawn: spawn_weapon::<WhipWielder>,
+            },
+            (swing_whips, sweep_lashes).chain(),
+        );
+    }
+}
+
+/// Sweep a lash across the facing direction, alternating sides for every extra lash.
+fn swing_whips(
+    mut commands: Commands,
+    time: Res<Time>,
+    player_query: Query<&Facing, With<Player>>,
+    mut wielder_query: Query<(&Parent, &mut WeaponCooldown), With<WhipWielder>>,
+) {
+    for (parent, mut cooldown) in wielder_query.iter_mut() {
+        let Ok(facing) = player_query.get(parent.get()) else {
+            continue;
+        };
+        if !cooldown.tick(time.delta()) {
+            continue;
+        }
+
+        let stats = cooldown.stats();
+        for i in 0..stats.amount {
+            let side = if i % 2 == 0 { facing.0 } else { -facing.0 };
+            let lash = spawn_lash(&mut commands, side, stats.damage, stats.area);
+            commands.entity(parent.get()).add_child(lash);
+        }
+    }
+}
+
+/// Short-lived hitbox sweeping across `direction`, attached to the player so it follows them.
+fn spawn_lash(commands: &mut Commands, direction: Vec2, damage: f32, area: f32) -> Entity {
+    let size = Vec2::new(WHIP_LENGTH, WHIP_WIDTH) * area;
+    let lash = Lash {
+        start: direction.to_angle() - SWEEP_ARC / 2.0,
+        arc: SWEEP_ARC,
+        reach: WHIP_OFFSET + size.x / 2.0,
+        timer: Timer::from_seconds(LASH_DURATION, TimerMode::Once),
+    };
+    commands
+        .spawn(Projectile)
+        .insert(Damage(damage))
+        .insert(SpriteBundle {
+            sprite: Sprite {
+                color: Color::srgba(1.0, 0.9, 0.6, 0.8),
+                custom_size: Some(size),
+                ..default()
+            },
+            transform: lash.transform(),
+            ..default()
+        })
+        .insert(lash)
+        .insert(RigidBody::KinematicPositionBased)
+        .insert(Collider::cuboid(size.x / 2.0, size.y / 2.0))
+        .insert(Sensor)
+        .insert(Lifetime(Timer::from_seconds(LASH_DURATION, TimerMode::Once)))
+        .id()
+}
+
+/// Lashes are children of the player, so this only sets the local transform.
+fn sweep_lashes(time: Res<Time>, mut lash_query: Query<(&mut Lash, &mut Transform)>) {
+    for (mut lash, mut transform) in lash_query.iter_mut() {
+        lash.timer.tick(time.delta());
+        *transform = lash.transform();
+    }
+}